@@ -0,0 +1,330 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+    },
+
+    error::{Error as StdError},
+};
+
+use ring::{
+
+    digest::{SHA1_FOR_LEGACY_USE_ONLY, digest},
+    rand::{SecureRandom, SystemRandom},
+};
+
+use x509_parser::{
+
+    x509::{SubjectPublicKeyInfo},
+    prelude::{FromDer},
+};
+
+use crate::{
+
+    certificate::{
+
+        CertificateExtendedKeyUsage,
+        CertificateAlternateName,
+        CertificateKeyUsage,
+        CertificateExtension,
+        CertificateValidity,
+        CertificateName,
+        Certificate,
+    },
+
+    signature::{
+
+        SignatureError,
+        KeyAlgorithm,
+        SigningKey,
+    },
+
+    request::{CertificateRequest},
+    encoding::{self},
+};
+
+/// Object identifier of the `basicConstraints` extension.
+const BASIC_CONSTRAINTS_OID: &str = "2.5.29.19";
+
+/// Object identifier of the `subjectKeyIdentifier` extension.
+const SUBJECT_KEY_IDENTIFIER_OID: &str = "2.5.29.14";
+
+/// Object identifier of the `authorityKeyIdentifier` extension.
+const AUTHORITY_KEY_IDENTIFIER_OID: &str = "2.5.29.35";
+
+/// Reason a certificate or certificate signing request could not be built.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuilderError {
+    /// An alternate name is of a type that cannot be encoded, such as a directory name.
+    UnsupportedName,
+    /// An alternate name is not valid for its type, such as a hostname, email or `URI` that
+    /// is not `ASCII`, or a malformed address.
+    InvalidName,
+    /// An extended key usage or extension has a malformed object identifier.
+    InvalidObjectIdentifier,
+    /// No subject public key was given for the certificate.
+    MissingPublicKey,
+    /// The signing key does not match the public key of the issuer, or of the certificate
+    /// itself when it is self-signed.
+    KeyMismatch,
+    /// A random serial number could not be generated.
+    Random,
+    /// The key could not sign, or the result could not be encoded.
+    Signature(SignatureError),
+}
+
+impl FmtDisplay for BuilderError {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        match self {
+            BuilderError::UnsupportedName => formatter.write_str("alternate name type is not supported"),
+            BuilderError::InvalidName => formatter.write_str("alternate name is not valid for its type"),
+            BuilderError::InvalidObjectIdentifier => formatter.write_str("object identifier is malformed"),
+            BuilderError::MissingPublicKey => formatter.write_str("subject public key is missing"),
+            BuilderError::KeyMismatch => formatter.write_str("signing key does not match the issuer public key"),
+            BuilderError::Random => formatter.write_str("random serial number could not be generated"),
+            BuilderError::Signature(error) => FmtDisplay::fmt(error, formatter),
+        }
+    }
+}
+
+impl StdError for BuilderError {}
+
+impl From<SignatureError> for BuilderError {
+    fn from(error: SignatureError) -> BuilderError {
+        BuilderError::Signature(error)
+    }
+}
+
+/// Builder of `X.509` version 3 certificates, giving a pending certificate to be signed by
+/// the issuer. Certificates without an issuer are self-signed.
+#[derive(Clone, Debug)]
+pub struct CertificateBuilder {
+    subject: CertificateName,
+    validity: CertificateValidity,
+    issuer: Option<Certificate>,
+    serial: Option<Vec<u8>>,
+    public_key: Option<Vec<u8>>,
+    basic_constraints: Option<(bool, Option<u32>)>,
+    alternate_names: Vec<CertificateAlternateName>,
+    key_usages: Vec<CertificateKeyUsage>,
+    extended_key_usages: Vec<CertificateExtendedKeyUsage>,
+    extensions: Vec<CertificateExtension>,
+}
+
+impl CertificateBuilder {
+    pub fn new(subject: CertificateName, validity: CertificateValidity) -> CertificateBuilder {
+        CertificateBuilder {
+            subject,
+            validity,
+            issuer: None,
+            serial: None,
+            public_key: None,
+            basic_constraints: None,
+            alternate_names: Default::default(),
+            key_usages: Default::default(),
+            extended_key_usages: Default::default(),
+            extensions: Default::default(),
+        }
+    }
+
+    /// Start from a certificate signing request, taking the subject, public key, alternate
+    /// names and key usages requested in it. The self-signature of the request is verified.
+    pub fn from_request(request: &CertificateRequest, validity: CertificateValidity) -> Result<CertificateBuilder, SignatureError> {
+        request.verify_signature()?;
+
+        let mut builder = CertificateBuilder::new(request.subject(), validity)
+            .with_public_key(request.public_key_der_encoded());
+
+        builder.alternate_names = request.subject_alternate_names().to_vec();
+        builder.key_usages = request.key_usages().to_vec();
+        builder.extended_key_usages = request.extended_key_usages().to_vec();
+
+        Ok(builder)
+    }
+
+    /// Issue the certificate from the given issuer, whose key must sign it. The issuer name
+    /// and authority key identifier are taken from the issuer certificate.
+    pub fn with_issuer(mut self, issuer: &Certificate) -> CertificateBuilder {
+        self.issuer = Some(issuer.clone());
+        self
+    }
+
+    /// Big-endian serial number. A random serial number is used when none is given.
+    pub fn with_serial(mut self, serial: &[u8]) -> CertificateBuilder {
+        self.serial = Some(encoding::unsigned(serial));
+        self
+    }
+
+    /// `DER` encoded subject public key info of the certificate. The public key given when
+    /// building, or of the signing key, is used when none is given.
+    pub fn with_public_key(mut self, public_key: &[u8]) -> CertificateBuilder {
+        self.public_key = Some(public_key.to_vec());
+        self
+    }
+
+    /// Critical `basicConstraints` extension, marking a certificate authority with an
+    /// optional limit on the intermediates below it, or an end entity.
+    pub fn with_basic_constraints(mut self, authority: bool, path_length: Option<u32>) -> CertificateBuilder {
+        self.basic_constraints = Some((authority, path_length.filter(|_| authority)));
+        self
+    }
+
+    /// Add an alternate name. Directory names are not supported.
+    pub fn with_alternate_name(mut self, name: CertificateAlternateName) -> CertificateBuilder {
+        self.alternate_names.push(name);
+        self
+    }
+
+    pub fn with_key_usage(mut self, usage: CertificateKeyUsage) -> CertificateBuilder {
+        self.key_usages.push(usage);
+        self
+    }
+
+    pub fn with_extended_key_usage(mut self, usage: CertificateExtendedKeyUsage) -> CertificateBuilder {
+        self.extended_key_usages.push(usage);
+        self
+    }
+
+    /// Add an extension, replacing the one the builder would derive for the same object
+    /// identifier.
+    pub fn with_extension(mut self, extension: CertificateExtension) -> CertificateBuilder {
+        self.extensions.retain(|existing| existing.oid() != extension.oid());
+        self.extensions.push(extension);
+        self
+    }
+
+    /// Encode the to-be-signed certificate for the signature algorithm of the key that will
+    /// sign it, giving a pending certificate. The given subject public key is used when none
+    /// was given to the builder, as for self-signed certificates.
+    pub fn build(&self, algorithm: KeyAlgorithm, public_key: Option<&[u8]>) -> Result<Certificate, BuilderError> {
+        let public_key = match self.public_key {
+            Some(ref public_key) => public_key.clone(),
+            None => public_key.ok_or(BuilderError::MissingPublicKey)?.to_vec(),
+        };
+
+        let key_identifier = key_identifier(public_key.as_slice())
+            .ok_or(SignatureError::BadEncoding)?;
+
+        let (issuer, authority_key_identifier) = match self.issuer {
+            Some(ref issuer) => (
+                issuer.subject_der_encoded().ok_or(SignatureError::BadEncoding)?,
+                issuer.subject_key_identifier(),
+            ),
+            None => (self.subject.der_encoded(), Some(key_identifier.clone())),
+        };
+
+        let serial = match self.serial {
+            Some(ref serial) => serial.clone(),
+            None => serial().ok_or(BuilderError::Random)?,
+        };
+
+        let mut extensions = Vec::new();
+
+        if let Some((authority, path_length)) = self.basic_constraints {
+            let mut constraints = Vec::new();
+            if authority {
+                constraints.extend(encoding::element(0x01, &[0xFF]));
+            }
+
+            if let Some(path_length) = path_length {
+                constraints.extend(encoding::integer(&(path_length.to_be_bytes())));
+            }
+
+            let value = encoding::element(0x30, constraints.as_slice());
+            extensions.push(CertificateExtension::new(BASIC_CONSTRAINTS_OID, true, value.as_slice()));
+        }
+
+        if !self.key_usages.is_empty() {
+            extensions.push(CertificateExtension::key_usages(self.key_usages.as_slice()));
+        }
+
+        if !self.extended_key_usages.is_empty() {
+            extensions.push(CertificateExtension::extended_key_usages(self.extended_key_usages.as_slice())?);
+        }
+
+        let value = encoding::element(0x04, key_identifier.as_slice());
+        extensions.push(CertificateExtension::new(SUBJECT_KEY_IDENTIFIER_OID, false, value.as_slice()));
+
+        if let Some(identifier) = authority_key_identifier {
+            let value = encoding::element(0x30, encoding::element(0x80, identifier.as_slice()).as_slice());
+            extensions.push(CertificateExtension::new(AUTHORITY_KEY_IDENTIFIER_OID, false, value.as_slice()));
+        }
+
+        if !self.alternate_names.is_empty() {
+            extensions.push(CertificateExtension::alternate_names(self.alternate_names.as_slice(), self.subject.is_empty())?);
+        }
+
+        extensions.retain(|extension| self.extensions.iter().all(|other| other.oid() != extension.oid()));
+        extensions.extend(self.extensions.iter().cloned());
+
+        let extensions: Vec<Vec<u8>> = extensions.iter()
+            .map(|extension| extension.der_encoded())
+            .collect::<Option<_>>()
+            .ok_or(BuilderError::InvalidObjectIdentifier)?;
+
+        let validity = encoding::element(0x30, [
+            encoding::time_element(self.validity.time_begin()),
+            encoding::time_element(self.validity.time_end()),
+        ].concat().as_slice());
+
+        let tbs = encoding::element(0x30, [
+            encoding::element(0xA0, encoding::integer(&[0x02]).as_slice()),
+            encoding::integer(serial.as_slice()),
+            algorithm.der_encoded(),
+            issuer,
+            validity,
+            self.subject.der_encoded(),
+            public_key,
+            encoding::element(0xA3, encoding::element(0x30, extensions.concat().as_slice()).as_slice()),
+        ].concat().as_slice());
+
+        Certificate::parse(tbs.as_slice())
+            .ok_or(BuilderError::Signature(SignatureError::BadEncoding))
+    }
+
+    /// Build the certificate and sign it with the key of the issuer, or the key of the
+    /// certificate itself when it is self-signed. A self-signed certificate without a
+    /// subject public key takes the public key of the signing key.
+    pub fn sign(&self, key: &SigningKey) -> Result<Certificate, BuilderError> {
+        let public_key = key.public_key_der_encoded();
+
+        let expected = match (&(self.issuer), &(self.public_key)) {
+            (Some(issuer), _) => issuer.public_key_der_encoded().ok_or(SignatureError::BadEncoding)?,
+            (None, Some(public_key)) => public_key.clone(),
+            (None, None) => public_key.clone(),
+        };
+
+        let signing = key_identifier(public_key.as_slice()).ok_or(SignatureError::BadEncoding)?;
+        if key_identifier(expected.as_slice()).ok_or(SignatureError::BadEncoding)? != signing {
+            return Err(BuilderError::KeyMismatch)
+        }
+
+        let fallback = match self.issuer {
+            Some(..) => None,
+            None => Some(public_key.as_slice()),
+        };
+
+        Ok(self.build(key.algorithm(), fallback)?.sign(key)?)
+    }
+}
+
+/// Key identifier of a `DER` encoded subject public key info, the SHA-1 hash of the public
+/// key bits as described in RFC 5280.
+fn key_identifier(public_key: &[u8]) -> Option<Vec<u8>> {
+    let (_, public_key) = SubjectPublicKeyInfo::from_der(public_key).ok()?;
+    let hash = digest(&SHA1_FOR_LEGACY_USE_ONLY, public_key.subject_public_key.data.as_ref());
+
+    Some(hash.as_ref().to_vec())
+}
+
+/// Random positive serial number of 16 octets.
+fn serial() -> Option<Vec<u8>> {
+    let mut serial = vec![0u8; 16];
+    SystemRandom::new().fill(serial.as_mut_slice()).ok()?;
+
+    serial[0] = (serial[0] & 0x7F) | 0x40;
+    Some(serial)
+}
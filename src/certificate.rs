@@ -7,29 +7,20 @@ use std::{
         Debug as FmtDebug,
     },
 
-    time::{SystemTime},
     net::{IpAddr},
 };
 
 use chrono::{
     
-    NaiveDateTime,
     DateTime, 
+    TimeZone,
     Utc,
 };
 
-use rustls_native_certs::{Certificate as TlsNativeCertificate};
+use ring::digest::{
 
-use rustls::{
-
-    server::{
-        AllowAnyAuthenticatedClient as TlsAllowAnyAuthenticatedClient, 
-        ClientCertVerifier as TlsClientCertVerifier,
-    },
-
-    OwnedTrustAnchor as TlsOwnedTrustAnchor,
-    RootCertStore as TlsRootCertStore,
-    Certificate as TlsCertificate,
+    SHA256,
+    digest,
 };
 
 use serde::{
@@ -38,16 +29,77 @@ use serde::{
     Serialize,
 };
 
-use webpki_roots::{TLS_SERVER_ROOTS};
+use crate::{
+
+    verification::{
+
+        VerificationReport,
+        VerificationError,
+        ServerVerification,
+        Purpose,
+        self,
+    },
+
+    signature::{
+
+        SignatureError,
+        SigningKey,
+        self,
+    },
+
+    builder::{BuilderError},
+    encoding::{self},
+    path::{self},
+
+    store::{
+
+        TrustStore,
+        self,
+    },
+
+    revocation::{
+
+        RevocationOptions,
+        RevocationStatus,
+        OcspResponse,
+        RevocationList,
+        RevocationSet,
+        self,
+    },
+
+    transparency::{
+
+        SignedCertificateTimestamp,
+        TimestampVerification,
+        CtPolicyReport,
+        CtLogList,
+        CtPolicy,
+        LogKey,
+        policy,
+        self,
+    },
+};
 
 use x509_parser::prelude::{
 
+    ExtendedKeyUsage,
+    ParsedExtension,
     X509Certificate, 
     TbsCertificate, 
     GeneralName, 
+    KeyUsage,
     FromDer,
 };
 
+/// Object identifier of the `subjectAltName` extension.
+const ALTERNATE_NAMES_OID: &str = "2.5.29.17";
+
+/// Object identifier of the `keyUsage` extension.
+const KEY_USAGE_OID: &str = "2.5.29.15";
+
+/// Object identifier of the `extKeyUsage` extension.
+const EXTENDED_KEY_USAGE_OID: &str = "2.5.29.37";
+
 #[derive(Clone, Copy, Debug)]
 #[derive(Serialize, Deserialize)]
 pub struct CertificateValidity {
@@ -56,22 +108,28 @@ pub struct CertificateValidity {
 }
 
 impl CertificateValidity {
+    /// Create a validity period between two times, in whole seconds.
+    pub fn new(begin: DateTime<Utc>, end: DateTime<Utc>) -> CertificateValidity {
+        CertificateValidity::from_timestamps(begin.timestamp(), end.timestamp())
+    }
+
     pub(crate) fn from_timestamps(begin: i64, end: i64) -> CertificateValidity {
         CertificateValidity { 
-            begin: DateTime::from_utc(NaiveDateTime::from_timestamp_opt(begin.min(end), 0)
-                .unwrap_or_default(), Utc), 
-            end: DateTime::from_utc(NaiveDateTime::from_timestamp_opt(end.max(begin), 0)
-                .unwrap_or_default(), Utc), 
+            begin: Utc.timestamp_opt(begin.min(end), 0).single()
+                .unwrap_or_default(), 
+            end: Utc.timestamp_opt(end.max(begin), 0).single()
+                .unwrap_or_default(), 
         }
     }
 
     pub fn is_within_valid_time(&self) -> bool {
-        let now = Utc::now();
+        self.is_within_valid_time_at(Utc::now())
+    }
 
-        if self.end > now {
-            if self.begin < now {
-                return true
-            }
+    /// Check whether the given time falls within the validity period.
+    pub fn is_within_valid_time_at(&self, time: DateTime<Utc>) -> bool {
+        if self.end > time && self.begin < time {
+            return true
         }
 
         false
@@ -82,7 +140,7 @@ impl CertificateValidity {
     }
 
     pub fn time_begin(&self) -> DateTime<Utc> {
-        self.begin.clone()
+        self.begin
     }
 
     pub fn timestamp_end(&self) -> i64 {
@@ -90,11 +148,11 @@ impl CertificateValidity {
     }
 
     pub fn time_end(&self) -> DateTime<Utc> {
-        self.end.clone()
+        self.end
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[derive(Serialize, Deserialize)]
 pub enum CertificateAlternateName {
     Directory(String),
@@ -105,6 +163,7 @@ pub enum CertificateAlternateName {
 }
 
 impl<'a> CertificateAlternateName {
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&'a self) -> String{
         match self {
             CertificateAlternateName::Directory(ref string) => string.clone(),
@@ -126,6 +185,327 @@ impl<'a> CertificateAlternateName {
     }
 }
 
+/// Distinguished name of a certificate subject or issuer, limited to the attributes the
+/// crate exposes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub struct CertificateName {
+    pub(crate) name: Option<String>,
+    pub(crate) country: Option<String>,
+    pub(crate) state: Option<String>,
+    pub(crate) organization: Option<String>,
+    pub(crate) organizational_unit: Option<String>,
+}
+
+impl CertificateName {
+    /// Create a name with the given common name.
+    pub fn new(name: &str) -> CertificateName {
+        CertificateName { name: Some(name.to_owned()), ..Default::default() }
+    }
+
+    pub fn with_country(mut self, country: &str) -> CertificateName {
+        self.country = Some(country.to_owned());
+        self
+    }
+
+    pub fn with_state(mut self, state: &str) -> CertificateName {
+        self.state = Some(state.to_owned());
+        self
+    }
+
+    pub fn with_organization(mut self, organization: &str) -> CertificateName {
+        self.organization = Some(organization.to_owned());
+        self
+    }
+
+    pub fn with_organizational_unit(mut self, organizational_unit: &str) -> CertificateName {
+        self.organizational_unit = Some(organizational_unit.to_owned());
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        if let Some(ref name) = self.name {
+            return Some(name.as_str())
+        }
+
+        None
+    }
+
+    pub fn country(&self) -> Option<&str> {
+        if let Some(ref country) = self.country {
+            return Some(country.as_str())
+        }
+
+        None
+    }
+
+    pub fn state(&self) -> Option<&str> {
+        if let Some(ref state) = self.state {
+            return Some(state.as_str())
+        }
+
+        None
+    }
+
+    pub fn organization(&self) -> Option<&str> {
+        if let Some(ref organization) = self.organization {
+            return Some(organization.as_str())
+        }
+
+        None
+    }
+
+    pub fn organizational_unit(&self) -> Option<&str> {
+        if let Some(ref organizational_unit) = self.organizational_unit {
+            return Some(organizational_unit.as_str())
+        }
+
+        None
+    }
+
+    /// Whether the name has no attributes at all.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.country.is_none() && self.state.is_none()
+            && self.organization.is_none() && self.organizational_unit.is_none()
+    }
+
+    /// `DER` encoded name, with one attribute per relative distinguished name from the
+    /// country down to the common name.
+    pub(crate) fn der_encoded(&self) -> Vec<u8> {
+        let attributes = [
+            (&[0x55, 0x04, 0x06], 0x13, &(self.country)),
+            (&[0x55, 0x04, 0x08], 0x0C, &(self.state)),
+            (&[0x55, 0x04, 0x0A], 0x0C, &(self.organization)),
+            (&[0x55, 0x04, 0x0B], 0x0C, &(self.organizational_unit)),
+            (&[0x55, 0x04, 0x03], 0x0C, &(self.name)),
+        ];
+
+        let names: Vec<u8> = attributes.iter().filter_map(|(oid, tag, value)| {
+            let value = value.as_ref()?;
+
+            let attribute = encoding::element(0x30, [
+                encoding::element(0x06, &oid[..]),
+                encoding::element(*tag, value.as_bytes()),
+            ].concat().as_slice());
+
+            Some(encoding::element(0x31, attribute.as_slice()))
+        }).flatten().collect();
+
+        encoding::element(0x30, names.as_slice())
+    }
+}
+
+/// Key usage of a certificate, as defined in RFC 5280. Declared in the order of the bits
+/// of the extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub enum CertificateKeyUsage {
+    DigitalSignature,
+    ContentCommitment,
+    KeyEncipherment,
+    DataEncipherment,
+    KeyAgreement,
+    KeyCertSign,
+    CrlSign,
+    EncipherOnly,
+    DecipherOnly,
+}
+
+/// Extended key usage of a certificate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub enum CertificateExtendedKeyUsage {
+    Any,
+    ServerAuth,
+    ClientAuth,
+    CodeSigning,
+    EmailProtection,
+    TimeStamping,
+    OcspSigning,
+    /// Purpose identified by a dotted object identifier.
+    Other(String),
+}
+
+/// Extension of a certificate or certificate request, with its `DER` encoded value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub struct CertificateExtension {
+    oid: String,
+    critical: bool,
+    value: Vec<u8>,
+}
+
+impl CertificateExtension {
+    /// Create an extension from a dotted object identifier and the `DER` encoded value.
+    pub fn new(oid: &str, critical: bool, value: &[u8]) -> CertificateExtension {
+        CertificateExtension { oid: oid.to_owned(), critical, value: value.to_vec() }
+    }
+
+    /// Dotted object identifier of the extension.
+    pub fn oid(&self) -> &str {
+        self.oid.as_str()
+    }
+
+    pub fn critical(&self) -> bool {
+        self.critical
+    }
+
+    pub fn value(&self) -> &[u8] {
+        self.value.as_slice()
+    }
+
+    /// `subjectAltName` extension with the given alternate names. Directory names cannot be
+    /// encoded from their string form, and hostnames, emails and `URI`s must be `ASCII`.
+    pub(crate) fn alternate_names(names: &[CertificateAlternateName], critical: bool) -> Result<CertificateExtension, BuilderError> {
+        let ia5 = |tag: u8, value: &str| match value.is_ascii() {
+            true => Ok(encoding::element(tag, value.as_bytes())),
+            false => Err(BuilderError::InvalidName),
+        };
+
+        let names: Vec<Vec<u8>> = names.iter().map(|name| match name {
+            CertificateAlternateName::Email(ref email) => ia5(0x81, email),
+            CertificateAlternateName::Hostname(ref hostname) => ia5(0x82, hostname),
+            CertificateAlternateName::Uri(ref uri) => ia5(0x86, uri),
+            CertificateAlternateName::Address(ref address) => match address.parse().map_err(|_| BuilderError::InvalidName)? {
+                IpAddr::V4(address) => Ok(encoding::element(0x87, &(address.octets()))),
+                IpAddr::V6(address) => Ok(encoding::element(0x87, &(address.octets()))),
+            },
+            CertificateAlternateName::Directory(..) => Err(BuilderError::UnsupportedName),
+        }).collect::<Result<_, _>>()?;
+
+        let value = encoding::element(0x30, names.concat().as_slice());
+        Ok(CertificateExtension::new(ALTERNATE_NAMES_OID, critical, value.as_slice()))
+    }
+
+    /// Critical `keyUsage` extension with the given key usages.
+    pub(crate) fn key_usages(usages: &[CertificateKeyUsage]) -> CertificateExtension {
+        let mut bits = [0u8; 2];
+        for usage in usages.iter() {
+            let bit = *usage as usize;
+            bits[bit / 8] |= 0x80 >> (bit % 8);
+        }
+
+        let length = if bits[1] != 0 { 2 } else if bits[0] != 0 { 1 } else { 0 };
+        let unused = bits[..length].last()
+            .map(|octet| octet.trailing_zeros() as u8)
+            .unwrap_or(0);
+
+        let value = encoding::element(0x03, [&[unused], &(bits[..length])].concat().as_slice());
+        CertificateExtension::new(KEY_USAGE_OID, true, value.as_slice())
+    }
+
+    /// `extKeyUsage` extension with the given extended key usages.
+    pub(crate) fn extended_key_usages(usages: &[CertificateExtendedKeyUsage]) -> Result<CertificateExtension, BuilderError> {
+        let oids: Vec<Vec<u8>> = usages.iter().map(|usage| {
+            let oid = match usage {
+                CertificateExtendedKeyUsage::Any => "2.5.29.37.0",
+                CertificateExtendedKeyUsage::ServerAuth => "1.3.6.1.5.5.7.3.1",
+                CertificateExtendedKeyUsage::ClientAuth => "1.3.6.1.5.5.7.3.2",
+                CertificateExtendedKeyUsage::CodeSigning => "1.3.6.1.5.5.7.3.3",
+                CertificateExtendedKeyUsage::EmailProtection => "1.3.6.1.5.5.7.3.4",
+                CertificateExtendedKeyUsage::TimeStamping => "1.3.6.1.5.5.7.3.8",
+                CertificateExtendedKeyUsage::OcspSigning => "1.3.6.1.5.5.7.3.9",
+                CertificateExtendedKeyUsage::Other(ref oid) => oid.as_str(),
+            };
+
+            encoding::oid_from_string(oid)
+                .map(|oid| encoding::element(0x06, oid.as_slice()))
+                .ok_or(BuilderError::InvalidObjectIdentifier)
+        }).collect::<Result<_, _>>()?;
+
+        let value = encoding::element(0x30, oids.concat().as_slice());
+        Ok(CertificateExtension::new(EXTENDED_KEY_USAGE_OID, false, value.as_slice()))
+    }
+
+    /// `DER` encoded extension, or nothing when the object identifier is malformed.
+    pub(crate) fn der_encoded(&self) -> Option<Vec<u8>> {
+        let mut extension = encoding::element(0x06, encoding::oid_from_string(self.oid())?.as_slice());
+        if self.critical {
+            extension.extend(encoding::element(0x01, &[0xFF]));
+        }
+
+        extension.extend(encoding::element(0x04, self.value()));
+        Some(encoding::element(0x30, extension.as_slice()))
+    }
+}
+
+pub(crate) fn key_usages(usage: &KeyUsage) -> Vec<CertificateKeyUsage> {
+    [
+        (usage.digital_signature(), CertificateKeyUsage::DigitalSignature),
+        (usage.non_repudiation(), CertificateKeyUsage::ContentCommitment),
+        (usage.key_encipherment(), CertificateKeyUsage::KeyEncipherment),
+        (usage.data_encipherment(), CertificateKeyUsage::DataEncipherment),
+        (usage.key_agreement(), CertificateKeyUsage::KeyAgreement),
+        (usage.key_cert_sign(), CertificateKeyUsage::KeyCertSign),
+        (usage.crl_sign(), CertificateKeyUsage::CrlSign),
+        (usage.encipher_only(), CertificateKeyUsage::EncipherOnly),
+        (usage.decipher_only(), CertificateKeyUsage::DecipherOnly),
+    ].into_iter().filter_map(|(allowed, usage)| allowed.then_some(usage)).collect()
+}
+
+pub(crate) fn extended_key_usages(usage: &ExtendedKeyUsage) -> Vec<CertificateExtendedKeyUsage> {
+    let mut usages: Vec<CertificateExtendedKeyUsage> = [
+        (usage.any, CertificateExtendedKeyUsage::Any),
+        (usage.server_auth, CertificateExtendedKeyUsage::ServerAuth),
+        (usage.client_auth, CertificateExtendedKeyUsage::ClientAuth),
+        (usage.code_signing, CertificateExtendedKeyUsage::CodeSigning),
+        (usage.email_protection, CertificateExtendedKeyUsage::EmailProtection),
+        (usage.time_stamping, CertificateExtendedKeyUsage::TimeStamping),
+        (usage.ocsp_signing, CertificateExtendedKeyUsage::OcspSigning),
+    ].into_iter().filter_map(|(allowed, usage)| allowed.then_some(usage)).collect();
+
+    usages.extend(usage.other.iter().map(|oid| CertificateExtendedKeyUsage::Other(oid.to_id_string())));
+    usages
+}
+
+/// Alternate names of a `subjectAltName` extension, except those repeating the subject name.
+pub(crate) fn alternate_names(names: &[GeneralName], subject_name: Option<&str>) -> Vec<CertificateAlternateName> {
+    names.iter().filter_map(|name| match name {
+        GeneralName::DirectoryName(name) => Some({
+            CertificateAlternateName::Directory(name.to_string())
+        }),
+        GeneralName::RFC822Name(name) => Some({
+            CertificateAlternateName::Email(name.to_string())
+        }),
+        GeneralName::IPAddress(octets) => Some({
+            CertificateAlternateName::Address(match octets {
+                octets if octets.len() == 4 => {
+                    let mut array: [u8; 4] = Default::default();
+                    array.copy_from_slice(octets);
+                    IpAddr::from(array).to_string()
+                },
+                octets if octets.len() == 16 => {
+                    let mut array: [u8; 16] = Default::default();
+                    array.copy_from_slice(octets);
+                    IpAddr::from(array).to_string()
+                },
+                _ => return None
+            })
+        }),
+        GeneralName::DNSName(name) => Some({
+            CertificateAlternateName::Hostname(name.to_string())
+        }),
+        GeneralName::URI(name) => Some({
+            CertificateAlternateName::Uri(name.to_string())
+        }),
+        _ => None,
+    }).filter_map(|alternate| match alternate.clone() {
+        CertificateAlternateName::Directory(ref item) |
+        CertificateAlternateName::Hostname(ref item) |
+        CertificateAlternateName::Address(ref item) |
+        CertificateAlternateName::Email(ref item) |
+        CertificateAlternateName::Uri(ref item) => {
+            if let Some(subject) = subject_name {
+                if item == subject {
+                    return None
+                }
+            }
+    
+            Some(alternate)
+        }
+    }).collect()
+}
+
 #[derive(Clone)]
 #[derive(Serialize, Deserialize)]
 pub struct CertificateData {
@@ -298,110 +678,47 @@ impl Certificate {
 
         let issuer_name = certificate.issuer().iter_common_name()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
 
         let issuer_country = certificate.issuer().iter_country()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
 
         let issuer_state = certificate.issuer().iter_state_or_province()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
     
         let issuer_organization = certificate.issuer().iter_organization()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
 
         let issuer_organizational_unit = certificate.issuer().iter_organizational_unit()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
         
         let subject_name = certificate.subject().iter_common_name()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
 
         let subject_alternate = if let Ok(Some(extension)) = certificate.subject_alternative_name() {
-            extension.value.general_names.iter().filter_map(|name| match name {
-                GeneralName::DirectoryName(name) => Some({
-                    CertificateAlternateName::Directory(name.to_string())
-                }),
-                GeneralName::RFC822Name(name) => Some({
-                    CertificateAlternateName::Email(name.to_string())
-                }),
-                GeneralName::IPAddress(octets) => Some({
-                    CertificateAlternateName::Address(match octets {
-                        octets if octets.len() == 4 => {
-                            let mut array: [u8; 4] = Default::default();
-                            for i in 0..4 { array[i] = octets[i] }
-                            IpAddr::from(array).to_string()
-                        },
-                        octets if octets.len() == 16 => {
-                            let mut array: [u8; 16] = Default::default();
-                            for i in 0..16 { array[i] = octets[i] }
-                            IpAddr::from(array).to_string()
-                        },
-                        _ => return None
-                    })
-                }),
-                GeneralName::DNSName(name) => Some({
-                    CertificateAlternateName::Hostname(name.to_string())
-                }),
-                GeneralName::URI(name) => Some({
-                    CertificateAlternateName::Uri(name.to_string())
-                }),
-                _ => None,
-            }).filter_map(|alternate| match alternate.clone() {
-                CertificateAlternateName::Directory(ref item) |
-                CertificateAlternateName::Hostname(ref item) |
-                CertificateAlternateName::Address(ref item) |
-                CertificateAlternateName::Email(ref item) |
-                CertificateAlternateName::Uri(ref item) => {
-                    if let Some(ref subject) = subject_name {
-                        if item == subject.as_str() {
-                            return None
-                        }
-                    }
-    
-                    Some(alternate)
-                }
-            }).collect()
+            alternate_names(extension.value.general_names.as_slice(), subject_name.as_deref())
         } else { Default::default() };
 
         let subject_country = certificate.subject().iter_country()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
 
         let subject_state = certificate.subject().iter_state_or_province()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
 
         let subject_organization = certificate.subject().iter_organization()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
 
         let subject_organizational_unit = certificate.subject().iter_organizational_unit()
             .filter_map(|name| name.as_str().ok())
-            .next().and_then(|name| Some({
-                Some(name.to_owned())
-            })).unwrap_or(None);
+            .next().map(|name| name.to_owned());
     
         let validity = {
     
@@ -438,66 +755,39 @@ impl Certificate {
         else { Some(Certificate::Signed(data)) }
     }
 
-    pub fn verify_trust_chain_web_roots(&self, chain: &[Certificate]) -> bool {
-        let mut trust_store = TlsRootCertStore::empty();
-
-        trust_store.add_server_trust_anchors(TLS_SERVER_ROOTS.0.iter().map(|anchor| {
-            TlsOwnedTrustAnchor::from_subject_spki_name_constraints(anchor.subject, anchor.spki, anchor.name_constraints)
-        }));
-
-        let ref certificate = TlsCertificate(self.raw_der_encoded().to_owned());
-        let chain: Vec<TlsCertificate> = chain.iter().map(|certificate| {
-            TlsCertificate(certificate.raw_der_encoded().to_vec())
-        }).collect();
-
-        TlsAllowAnyAuthenticatedClient::new(trust_store)
-            .verify_client_cert(certificate, chain.as_slice(), SystemTime::now())
-            .is_ok()
+    /// Verify the chain of a client certificate against the trust store at the given time.
+    pub fn verify_client_trust_chain(&self, chain: &[Certificate], trust_store: &TrustStore, time: DateTime<Utc>) -> Result<VerificationReport, VerificationError> {
+        verification::verify(trust_store, self, chain, &(Purpose::Client), time)
     }
 
-    pub fn verify_trust_chain_system_roots(&self, chain: &[Certificate]) -> bool {
-        if let Ok(native) = rustls_native_certs::load_native_certs() {
-            let mut trust_store = TlsRootCertStore::empty();
-
-            for TlsNativeCertificate(data) in native.iter() {
-                let ref root = TlsCertificate(data.to_vec());
-                if let Err(..) = trust_store.add(root) {
-                    continue
-                }
-            }
-
-            let ref certificate = TlsCertificate(self.raw_der_encoded().to_owned());
-            let chain: Vec<TlsCertificate> = chain.iter().map(|certificate| {
-                TlsCertificate(certificate.raw_der_encoded().to_vec())
-            }).collect();
-
-            return TlsAllowAnyAuthenticatedClient::new(trust_store)
-                .verify_client_cert(certificate, chain.as_slice(), SystemTime::now())
-                .is_ok()
-        }
-
-        false
+    /// Verify the chain of a server certificate against the trust store at the given time.
+    pub fn verify_server_trust_chain(&self, chain: &[Certificate], trust_store: &TrustStore, server: &ServerVerification, time: DateTime<Utc>) -> Result<VerificationReport, VerificationError> {
+        verification::verify(trust_store, self, chain, &(Purpose::Server(server)), time)
     }
 
-    pub fn verify_trust_chain(&self, chain: &[Certificate], roots: &[Certificate]) -> bool {
-        let mut trust_store = TlsRootCertStore::empty();
+    /// Verify the chain of a client certificate against the trust store at the given time,
+    /// and the revocation status of its certificates according to the revocation options.
+    pub fn verify_client_trust_chain_with_revocation(&self, chain: &[Certificate], trust_store: &TrustStore, revocation: &RevocationOptions, time: DateTime<Utc>) -> Result<VerificationReport, VerificationError> {
+        verification::verify_with_revocation(trust_store, self, chain, &(Purpose::Client), revocation, time)
+    }
 
-        for root in roots.iter() {
-            let ref certificate = TlsCertificate(root.raw_der_encoded().to_owned());
-            if let Err(..) = trust_store.add(certificate) {
-                return false
-            }
-        }
+    /// Verify the chain of a server certificate against the trust store at the given time,
+    /// and the revocation status of its certificates according to the revocation options.
+    pub fn verify_server_trust_chain_with_revocation(&self, chain: &[Certificate], trust_store: &TrustStore, server: &ServerVerification, revocation: &RevocationOptions, time: DateTime<Utc>) -> Result<VerificationReport, VerificationError> {
+        verification::verify_with_revocation(trust_store, self, chain, &(Purpose::Server(server)), revocation, time)
+    }
 
-        let ref certificate = TlsCertificate(self.raw_der_encoded().to_owned());
-        let chain: Vec<TlsCertificate> = chain.iter().map(|certificate| {
-            TlsCertificate(certificate.raw_der_encoded().to_vec())
-        }).collect();
+    /// Build every valid path from a client certificate to the trust store through an
+    /// unordered pool of intermediates, ranked by preference.
+    pub fn build_client_trust_paths(&self, pool: &[Certificate], trust_store: &TrustStore, time: DateTime<Utc>) -> Vec<VerificationReport> {
+        path::build(self, pool, trust_store, &(Purpose::Client), time)
+    }
 
-        TlsAllowAnyAuthenticatedClient::new(trust_store)
-            .verify_client_cert(certificate, chain.as_slice(), SystemTime::now())
-            .is_ok()
-    } 
+    /// Build every valid path from a server certificate to the trust store through an
+    /// unordered pool of intermediates, ranked by preference.
+    pub fn build_server_trust_paths(&self, pool: &[Certificate], trust_store: &TrustStore, server: &ServerVerification, time: DateTime<Utc>) -> Vec<VerificationReport> {
+        path::build(self, pool, trust_store, &(Purpose::Server(server)), time)
+    }
 
     pub fn authority(&self) -> bool {
         match self {
@@ -603,4 +893,230 @@ impl Certificate {
             Certificate::Pending(data) => data.raw_der_encoded(),
         }
     }
-}
\ No newline at end of file
+
+    /// Verify that the certificate was signed by the key of the issuer.
+    pub fn verify_signed_by(&self, issuer: &Certificate) -> Result<(), SignatureError> {
+        let public_key = issuer.public_key_der_encoded()
+            .ok_or(SignatureError::BadEncoding)?;
+
+        self.verify_signed_by_key(public_key.as_slice())
+    }
+
+    /// Verify that the certificate was signed by a `DER` encoded subject public key info.
+    pub(crate) fn verify_signed_by_key(&self, public_key: &[u8]) -> Result<(), SignatureError> {
+        match self {
+            Certificate::Signed(data) => {
+                let (tbs, algorithm, signature) = signature::split_signed(data.raw_der_encoded())
+                    .ok_or(SignatureError::BadEncoding)?;
+
+                if signature::tbs_algorithm(tbs) != Some(algorithm) {
+                    return Err(SignatureError::AlgorithmMismatch)
+                }
+
+                signature::verify(public_key, algorithm, tbs, signature)
+            },
+            Certificate::Pending(..) => Err(SignatureError::Unsigned),
+        }
+    }
+
+    /// Verify a signature over the to-be-signed part of the certificate by the key of the
+    /// issuer, using the signature algorithm named in it. This is how a pending certificate
+    /// is checked against a signature supplied separately.
+    pub fn verify_signature_by(&self, issuer: &Certificate, signature: &[u8]) -> Result<(), SignatureError> {
+        let tbs = self.tbs_der_encoded()
+            .ok_or(SignatureError::BadEncoding)?;
+
+        let algorithm = signature::tbs_algorithm(tbs)
+            .ok_or(SignatureError::BadEncoding)?;
+
+        let public_key = issuer.public_key_der_encoded()
+            .ok_or(SignatureError::BadEncoding)?;
+
+        signature::verify(public_key.as_slice(), algorithm, tbs, signature)
+    }
+
+    /// Sign the to-be-signed part of the certificate with the key of the issuer, giving a
+    /// signed certificate. The key must match the signature algorithm named in it.
+    pub fn sign(&self, key: &SigningKey) -> Result<Certificate, SignatureError> {
+        let tbs = self.tbs_der_encoded()
+            .ok_or(SignatureError::BadEncoding)?;
+
+        let algorithm = key.algorithm_der_encoded();
+        if signature::tbs_algorithm(tbs) != Some(algorithm.as_slice()) {
+            return Err(SignatureError::AlgorithmMismatch)
+        }
+
+        let signature = key.sign(tbs)?;
+
+        let certificate = encoding::element(0x30, [
+            tbs,
+            algorithm.as_slice(),
+            encoding::element(0x03, [&[0x00], signature.as_slice()].concat().as_slice()).as_slice(),
+        ].concat().as_slice());
+
+        Certificate::parse(certificate.as_slice())
+            .ok_or(SignatureError::BadEncoding)
+    }
+
+    /// `OpenSSL` subject name hash, as used for `<subject_hash>.N` file names by `c_rehash`.
+    pub fn subject_hash(&self) -> Option<u32> {
+        store::name_hash(self.subject_der_encoded()?.as_slice())
+    }
+
+    /// `OpenSSL` issuer name hash, as used for `<issuer_hash>.rN` file names by `c_rehash`.
+    pub fn issuer_hash(&self) -> Option<u32> {
+        store::name_hash(self.issuer_der_encoded()?.as_slice())
+    }
+
+    /// Whether the certificate is an RFC 6962 precertificate, carrying the critical poison extension.
+    pub fn is_precertificate(&self) -> bool {
+        match self {
+            Certificate::Signed(data) => signature::split_signed(data.raw_der_encoded())
+                .and_then(|(tbs, _, _)| transparency::extensions(tbs))
+                .map(|extensions| extensions.iter().any(|(oid, element)| *oid == transparency::POISON_OID && transparency::is_critical(element)))
+                .unwrap_or(false),
+            Certificate::Pending(..) => false,
+        }
+    }
+
+    /// RFC 6962 final TBS, the `DER` encoded to-be-signed part without the embedded signed
+    /// certificate timestamp list and the poison. A precertificate and the certificate issued
+    /// from it share the same final TBS.
+    pub fn final_tbs_der_encoded(&self) -> Option<Vec<u8>> {
+        transparency::final_tbs(self.tbs_der_encoded()?)
+    }
+
+    /// Whether the certificate and the other certificate describe the same issuance, such as
+    /// a precertificate, signed or as a pending TBS from a log, and the final certificate.
+    /// Precertificates signed by a precertificate signing certificate carry a different
+    /// issuer and only match through the TBS logged for them.
+    pub fn is_same_issuance(&self, other: &Certificate) -> bool {
+        match (self.final_tbs_der_encoded(), other.final_tbs_der_encoded()) {
+            (Some(tbs), Some(other)) => tbs == other,
+            _ => false,
+        }
+    }
+
+    /// Stable identifier of the issuance, shared by a precertificate and its final certificate.
+    /// This is the hex encoded SHA-256 hash of the final TBS.
+    pub fn issuance_id(&self) -> Option<String> {
+        let tbs = self.final_tbs_der_encoded()?;
+        let hash = digest(&SHA256, tbs.as_slice());
+
+        Some(hash.as_ref().iter()
+            .map(|octet| format!("{:02x}", octet))
+            .collect())
+    }
+
+    /// Signed certificate timestamps embedded in the certificate, if it carries them.
+    pub fn signed_timestamps(&self) -> Option<Vec<SignedCertificateTimestamp>> {
+        let value = transparency::extension_value(self.tbs_der_encoded()?, transparency::TIMESTAMP_LIST_OID)?;
+        SignedCertificateTimestamp::parse_der_list(value)
+    }
+
+    /// Verify the signed certificate timestamps embedded in the certificate against the keys
+    /// of the logs that issued them, giving a verdict per timestamp.
+    pub fn verify_embedded_timestamps(&self, issuer: &Certificate, keys: &[LogKey]) -> Vec<TimestampVerification> {
+        self.signed_timestamps().unwrap_or_default().into_iter().map(|timestamp| {
+            timestamp.verification(keys, |timestamp, key| timestamp.verify_precert_entry(self, issuer, key))
+        }).collect()
+    }
+
+    /// Verify signed certificate timestamps delivered alongside the certificate, in the TLS
+    /// extension or an `OCSP` response, giving a verdict per timestamp.
+    pub fn verify_delivered_timestamps(&self, timestamps: &[SignedCertificateTimestamp], keys: &[LogKey]) -> Vec<TimestampVerification> {
+        timestamps.iter().cloned().map(|timestamp| {
+            timestamp.verification(keys, |timestamp, key| timestamp.verify_x509_entry(self, key))
+        }).collect()
+    }
+
+    /// Evaluate the embedded signed certificate timestamps against a certificate transparency
+    /// policy, with log keys, operators and states taken from the log list.
+    pub fn evaluate_ct_policy(&self, issuer: &Certificate, log_list: &CtLogList, policy: CtPolicy, time: DateTime<Utc>) -> CtPolicyReport {
+        policy::evaluate(self, issuer, log_list, policy, time)
+    }
+
+    /// Look up the certificate in a revocation list by issuer and serial number.
+    pub fn check_revocation(&self, list: &RevocationList) -> RevocationStatus {
+        revocation::check(self, list)
+    }
+
+    /// Look up the certificate in a revocation set merged from a base and delta revocation lists.
+    pub fn check_revocation_set(&self, set: &RevocationSet) -> RevocationStatus {
+        revocation::check_set(self, set)
+    }
+
+    /// Look up the certificate in an `OCSP` response by the identifier derived from the issuer.
+    /// The signature and freshness of the response are checked separately.
+    pub fn check_ocsp_response(&self, issuer: &Certificate, response: &OcspResponse) -> RevocationStatus {
+        revocation::check_response(self, issuer, response)
+    }
+
+    pub(crate) fn tbs_der_encoded(&self) -> Option<&[u8]> {
+        match self {
+            Certificate::Signed(data) => signature::split_signed(data.raw_der_encoded())
+                .map(|(tbs, _, _)| tbs),
+            Certificate::Pending(data) => Some(data.raw_der_encoded()),
+        }
+    }
+
+    pub(crate) fn tbs_certificate(&self) -> Option<TbsCertificate<'_>> {
+        match self {
+            Certificate::Signed(data) => {
+                if let Ok((_, certificate)) = X509Certificate::from_der(data.raw_der_encoded()) {
+                    return Some(certificate.tbs_certificate)
+                }
+
+                None
+            },
+            Certificate::Pending(data) => {
+                if let Ok((_, tbs_certificate)) = TbsCertificate::from_der(data.raw_der_encoded()) {
+                    return Some(tbs_certificate)
+                }
+
+                None
+            },
+        }
+    }
+
+    pub(crate) fn issuer_der_encoded(&self) -> Option<Vec<u8>> {
+        self.tbs_certificate().map(|certificate| {
+            certificate.issuer().as_raw().to_vec()
+        })
+    }
+
+    pub(crate) fn subject_der_encoded(&self) -> Option<Vec<u8>> {
+        self.tbs_certificate().map(|certificate| {
+            certificate.subject().as_raw().to_vec()
+        })
+    }
+
+    pub(crate) fn public_key_der_encoded(&self) -> Option<Vec<u8>> {
+        self.tbs_certificate().map(|certificate| {
+            certificate.public_key().raw.to_vec()
+        })
+    }
+
+    pub(crate) fn subject_key_identifier(&self) -> Option<Vec<u8>> {
+        let certificate = self.tbs_certificate()?;
+
+        let identifier = certificate.iter_extensions().find_map(|extension| match extension.parsed_extension() {
+            ParsedExtension::SubjectKeyIdentifier(identifier) => Some(identifier.0.to_vec()),
+            _ => None,
+        });
+
+        identifier
+    }
+
+    pub(crate) fn authority_key_identifier(&self) -> Option<Vec<u8>> {
+        let certificate = self.tbs_certificate()?;
+
+        let identifier = certificate.iter_extensions().find_map(|extension| match extension.parsed_extension() {
+            ParsedExtension::AuthorityKeyIdentifier(identifier) => identifier.key_identifier.as_ref()
+                .map(|identifier| identifier.0.to_vec()),
+            _ => None,
+        });
+
+        identifier
+    }
+}
@@ -0,0 +1,192 @@
+use base64::{
+
+    engine::general_purpose::{STANDARD as BASE64},
+    Engine,
+};
+
+use chrono::{
+
+    NaiveDateTime,
+    DateTime,
+    Datelike,
+    TimeZone,
+    Utc,
+};
+
+/// Read a single `DER` element, returning its tag, contents and the remaining data.
+pub(crate) fn read_element(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, data) = data.split_first()?;
+    let (&first, data) = data.split_first()?;
+
+    let (length, data) = if first & 0x80 == 0 {
+        (first as usize, data)
+    }
+
+    else {
+
+        let octets = (first & 0x7F) as usize;
+        if octets == 0 || octets > 4 || data.len() < octets {
+            return None
+        }
+
+        let length = data[..octets].iter()
+            .fold(0usize, |length, octet| (length << 8) | *octet as usize);
+
+        (length, &(data[octets..]))
+    };
+
+    if data.len() < length {
+        return None
+    }
+
+    Some((tag, &(data[..length]), &(data[length..])))
+}
+
+/// Contents of a single `DER` element with the expected tag.
+pub(crate) fn element_contents(data: &[u8], expected: u8) -> Option<&[u8]> {
+    if let Some((tag, contents, _)) = read_element(data) {
+        if tag == expected {
+            return Some(contents)
+        }
+    }
+
+    None
+}
+
+/// Encode a single `DER` element with the given tag and contents.
+pub(crate) fn element(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![tag];
+
+    if contents.len() < 0x80 {
+        encoded.push(contents.len() as u8);
+    }
+
+    else {
+
+        let length = (contents.len() as u64).to_be_bytes();
+        let length: Vec<u8> = length.iter().copied()
+            .skip_while(|octet| *octet == 0)
+            .collect();
+
+        encoded.push(0x80 | length.len() as u8);
+        encoded.extend_from_slice(length.as_slice());
+    }
+
+    encoded.extend_from_slice(contents);
+    encoded
+}
+
+/// Dotted string form of the contents of an `OBJECT IDENTIFIER`.
+pub(crate) fn oid_to_string(contents: &[u8]) -> Option<String> {
+    if contents.last()? & 0x80 != 0 {
+        return None
+    }
+
+    let mut arcs: Vec<u64> = Vec::new();
+    let mut value: u64 = 0;
+
+    for octet in contents.iter() {
+        value = value.checked_mul(128)? | (*octet & 0x7F) as u64;
+        if *octet & 0x80 == 0 {
+            arcs.push(value);
+            value = 0;
+        }
+    }
+
+    let (root, second) = match arcs[0] {
+        arc if arc < 40 => (0, arc),
+        arc if arc < 80 => (1, arc - 40),
+        arc => (2, arc - 80),
+    };
+
+    let mut string = format!("{}.{}", root, second);
+    for arc in arcs.iter().skip(1) {
+        string.push_str(format!(".{}", arc).as_str());
+    }
+
+    Some(string)
+}
+
+/// Contents of an `OBJECT IDENTIFIER` from its dotted string form.
+pub(crate) fn oid_from_string(string: &str) -> Option<Vec<u8>> {
+    let arcs: Vec<u64> = string.split('.')
+        .map(|arc| arc.parse().ok())
+        .collect::<Option<_>>()?;
+
+    let (root, second) = match arcs.as_slice() {
+        [root, second, ..] if *root < 2 && *second < 40 => (*root, *second),
+        [2, second, ..] => (2, *second),
+        _ => return None,
+    };
+
+    let mut contents = Vec::new();
+    for arc in std::iter::once(root * 40 + second).chain(arcs.iter().skip(2).copied()) {
+        let mut octets = vec![(arc & 0x7F) as u8];
+        let mut value = arc >> 7;
+
+        while value > 0 {
+            octets.push((value & 0x7F) as u8 | 0x80);
+            value >>= 7;
+        }
+
+        contents.extend(octets.iter().rev());
+    }
+
+    Some(contents)
+}
+
+/// `PEM` encoding of `DER` encoded data under the given label.
+pub(crate) fn pem(label: &str, data: &[u8]) -> String {
+    let encoded = BASE64.encode(data);
+
+    let mut string = format!("-----BEGIN {}-----\n", label);
+    for line in encoded.as_bytes().chunks(64) {
+        string.push_str(String::from_utf8_lossy(line).as_ref());
+        string.push('\n');
+    }
+
+    string.push_str(format!("-----END {}-----\n", label).as_str());
+    string
+}
+
+/// Contents of a non-negative `INTEGER`, without leading zeros.
+pub(crate) fn unsigned(contents: &[u8]) -> Vec<u8> {
+    contents.iter().copied()
+        .skip_while(|octet| *octet == 0)
+        .collect()
+}
+
+/// Encode a non-negative `INTEGER` from its big-endian value.
+pub(crate) fn integer(value: &[u8]) -> Vec<u8> {
+    let value = unsigned(value);
+
+    match value.first() {
+        Some(first) if first & 0x80 == 0 => element(0x02, value.as_slice()),
+        _ => element(0x02, [&[0x00], value.as_slice()].concat().as_slice()),
+    }
+}
+
+/// Encode a `GeneralizedTime` element, with whole seconds.
+pub(crate) fn generalized_time_element(time: DateTime<Utc>) -> Vec<u8> {
+    element(0x18, time.format("%Y%m%d%H%M%SZ").to_string().as_bytes())
+}
+
+/// Encode a certificate validity time, as a `UTCTime` element from 1950 through 2049 and a
+/// `GeneralizedTime` element outside those years.
+pub(crate) fn time_element(time: DateTime<Utc>) -> Vec<u8> {
+    if (1950..2050).contains(&time.year()) {
+        return element(0x17, time.format("%y%m%d%H%M%SZ").to_string().as_bytes())
+    }
+
+    generalized_time_element(time)
+}
+
+/// Time in the contents of a `GeneralizedTime`, ignoring fractional seconds.
+pub(crate) fn generalized_time(contents: &[u8]) -> Option<DateTime<Utc>> {
+    let string = std::str::from_utf8(contents).ok()?
+        .strip_suffix('Z')?;
+
+    let string = string.split('.').next()?;
+    NaiveDateTime::parse_from_str(string, "%Y%m%d%H%M%S").ok()
+        .map(|time| Utc.from_utc_datetime(&time))
+}
@@ -0,0 +1,247 @@
+use std::{
+
+    io::{
+
+        Result as IoResult,
+        BufReader,
+        BufRead,
+        Write,
+        Read,
+    },
+
+    net::{
+
+        ToSocketAddrs,
+        TcpListener,
+        SocketAddr,
+        TcpStream,
+        Ipv4Addr,
+        Ipv6Addr,
+    },
+
+    sync::{
+
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+
+    thread::{JoinHandle, self},
+    time::{Duration},
+};
+
+/// Largest request body a local `HTTP` listener accepts.
+const MAXIMUM_BODY: usize = 1 << 20;
+
+/// Longest request line or header line a local `HTTP` listener accepts.
+const MAXIMUM_LINE: usize = 8 << 10;
+
+/// Most header lines a local `HTTP` listener accepts.
+const MAXIMUM_HEADERS: usize = 100;
+
+/// Request received by a local `HTTP` listener.
+pub(crate) struct HttpRequest {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) query: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub(crate) fn parameter(&self, name: &str) -> Option<&str> {
+        self.query.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Response to a request received by a local `HTTP` listener.
+pub(crate) struct HttpResponse {
+    pub(crate) status: u16,
+    pub(crate) content_type: &'static str,
+    pub(crate) body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub(crate) fn new(content_type: &'static str, body: Vec<u8>) -> HttpResponse {
+        HttpResponse { status: 200, content_type, body }
+    }
+
+    pub(crate) fn status(status: u16) -> HttpResponse {
+        HttpResponse { status, content_type: "text/plain", body: Vec::new() }
+    }
+}
+
+/// Decode `%XX` escapes in a `URL` component. A `+` is kept, as it is in paths.
+pub(crate) fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut remaining = value.as_bytes();
+
+    while let Some((&byte, rest)) = remaining.split_first() {
+        match byte {
+            b'%' => {
+                let hex = std::str::from_utf8(rest.get(..2)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                remaining = &(rest[2..]);
+            },
+            byte => {
+                bytes.push(byte);
+                remaining = rest;
+            },
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+/// Decode a query component, where a `+` stands for a space.
+fn query_decode(value: &str) -> Option<String> {
+    percent_decode(value.replace('+', " ").as_str())
+}
+
+/// Read a line of at most `MAXIMUM_LINE` octets, giving the status to respond with when
+/// it is longer.
+fn read_line(reader: &mut impl BufRead, line: &mut String, status: u16) -> Result<(), u16> {
+    reader.take(MAXIMUM_LINE as u64).read_line(line).map_err(|_| 400u16)?;
+
+    if line.len() >= MAXIMUM_LINE && !line.ends_with('\n') {
+        return Err(status)
+    }
+
+    Ok(())
+}
+
+/// Read a request, giving the status to respond with when it is malformed or too large.
+fn read_request(stream: &TcpStream) -> Result<HttpRequest, u16> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    read_line(&mut reader, &mut line, 400)?;
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or(400u16)?.to_owned();
+    let target = parts.next().ok_or(400u16)?;
+
+    let mut length = 0;
+    for count in 0.. {
+        let mut header = String::new();
+        read_line(&mut reader, &mut header, 431)?;
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break
+        }
+
+        if count >= MAXIMUM_HEADERS {
+            return Err(431)
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().map_err(|_| 400u16)?;
+            }
+        }
+    }
+
+    if length > MAXIMUM_BODY {
+        return Err(413)
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(body.as_mut_slice()).map_err(|_| 400u16)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query.split('&').filter(|pair| !pair.is_empty()).map(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        Some((query_decode(key)?, query_decode(value)?))
+    }).collect::<Option<Vec<(String, String)>>>().ok_or(400u16)?;
+
+    let path = percent_decode(path).ok_or(400u16)?;
+    Ok(HttpRequest { method, path, query, body })
+}
+
+fn write_response(mut stream: &TcpStream, response: HttpResponse) -> IoResult<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    };
+
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status, reason, response.content_type, response.body.len())?;
+
+    stream.write_all(response.body.as_slice())?;
+    stream.flush()
+}
+
+/// Local `HTTP` listener serving requests on a background thread until it is dropped.
+pub struct HttpServer {
+    address: SocketAddr,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HttpServer {
+    pub(crate) fn spawn(address: impl ToSocketAddrs, handler: impl Fn(&HttpRequest) -> HttpResponse + Send + 'static) -> IoResult<HttpServer> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = thread::spawn({
+            let running = running.clone();
+            move || {
+                for stream in listener.incoming() {
+                    if !running.load(Ordering::SeqCst) {
+                        break
+                    }
+
+                    if let Ok(stream) = stream {
+                        let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+                        let response = match read_request(&stream) {
+                            Ok(request) => handler(&request),
+                            Err(status) => HttpResponse::status(status),
+                        };
+
+                        let _ = write_response(&stream, response);
+                    }
+                }
+            }
+        });
+
+        Ok(HttpServer { address, running, thread: Some(thread) })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Base `URL` of the listener.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+}
+
+impl Drop for HttpServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+
+        // Wake the listener so it observes the shutdown, through loopback when it is bound
+        // to every interface.
+        let mut address = self.address;
+        if address.ip().is_unspecified() {
+            address.set_ip(match address {
+                SocketAddr::V4(..) => Ipv4Addr::LOCALHOST.into(),
+                SocketAddr::V6(..) => Ipv6Addr::LOCALHOST.into(),
+            });
+        }
+
+        let _ = TcpStream::connect_timeout(&address, Duration::from_secs(1));
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
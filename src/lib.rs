@@ -1,16 +1,185 @@
 pub mod certificate;
 pub use certificate::{
     
+    CertificateExtendedKeyUsage,
     CertificateAlternateName,
+    CertificateExtension,
+    CertificateKeyUsage,
     CertificateValidity,
     CertificateData,
+    CertificateName,
     Certificate,
 };
 
+pub mod request;
+pub use request::{
+
+    CertificateRequestBuilder,
+    CertificateRequest,
+};
+
+pub mod builder;
+pub use builder::{
+
+    CertificateBuilder,
+    BuilderError,
+};
+
+pub mod verification;
+pub use verification::{
+
+    VerificationReason,
+    VerificationReport,
+    VerificationError,
+    ServerVerification,
+};
+
+pub mod store;
+pub use store::{
+
+    TrustStoreReason,
+    TrustStoreError,
+    TrustStore,
+};
+
+pub mod signature;
+pub use signature::{
+
+    SignatureError,
+    KeyAlgorithm,
+    SigningKey,
+};
+
+pub mod transparency;
+pub use transparency::{
+
+    TimestampSignatureAlgorithm,
+    SignedCertificateTimestamp,
+    TimestampHashAlgorithm,
+    TimestampVerification,
+    CtTimestampExclusion,
+    ConsistencyProof,
+    TimestampVersion,
+    CtPolicyFailure,
+    CtPolicyReport,
+    TimestampError,
+    MemoryLogError,
+    SignedTreeHead,
+    InclusionProof,
+    CtLogInterval,
+    NoteSignature,
+    LogEntryType,
+    CtLogState,
+    MerkleTree,
+    Checkpoint,
+    CtLogList,
+    MemoryLog,
+    CtPolicy,
+    LogEntry,
+    TileLeaf,
+    LogKey,
+    CtLog,
+};
+
+pub mod revocation;
+pub use revocation::{
+
+    IssuingDistributionPoint,
+    RevocationReason,
+    RevokedCertificate,
+    OcspCertificateId,
+    OcspResponseStatus,
+    OcspSingleResponse,
+    OcspHashAlgorithm,
+    RevocationOptions,
+    RevocationPolicy,
+    RevocationStatus,
+    RevocationError,
+    RevocationList,
+    OcspResponder,
+    RevocationSet,
+    OcspResponse,
+    OcspRequest,
+    OcspError,
+};
+
+pub(crate) mod http;
+pub use http::{
+
+    HttpServer,
+};
+
+pub(crate) mod encoding;
+pub(crate) mod path;
+
 #[cfg(test)]
 mod tests {
 
-    use super::{Certificate};
+    use chrono::{
+
+        TimeZone,
+        Utc,
+    };
+
+    use super::{
+
+        TimestampSignatureAlgorithm,
+        TimestampHashAlgorithm,
+        TimestampVersion,
+        SignedCertificateTimestamp,
+        VerificationReason,
+        VerificationError,
+        TimestampError,
+        CtTimestampExclusion,
+        CtPolicyFailure,
+        CtLogState,
+        CtLogList,
+        ConsistencyProof,
+        SignedTreeHead,
+        InclusionProof,
+        MemoryLogError,
+        LogEntryType,
+        KeyAlgorithm,
+        SigningKey,
+        MerkleTree,
+        MemoryLog,
+        Checkpoint,
+        CtPolicy,
+        LogEntry,
+        TileLeaf,
+        LogKey,
+        ServerVerification,
+        RevocationReason,
+        OcspResponseStatus,
+        OcspHashAlgorithm,
+        RevocationOptions,
+        RevocationPolicy,
+        RevocationStatus,
+        RevocationError,
+        RevocationList,
+        RevocationSet,
+        OcspResponder,
+        OcspResponse,
+        OcspRequest,
+        OcspError,
+        RevokedCertificate,
+        TrustStoreReason,
+        SignatureError,
+        TrustStore,
+        Certificate,
+        CertificateAlternateName,
+        CertificateExtendedKeyUsage,
+        CertificateKeyUsage,
+        CertificateRequest,
+        CertificateRequestBuilder,
+        CertificateName,
+        BuilderError,
+        CertificateBuilder,
+        CertificateValidity,
+        CertificateExtension,
+
+        store::{TrustAnchor},
+    };
 
     #[test]
     fn parse_certificate() {
@@ -158,4 +327,1677 @@ mod tests {
             ]);
         }
     }
+
+    #[test]
+    fn verify_server_trust_chain() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let server = ServerVerification::new("server.certain.test").unwrap();
+        assert!(certificate.verify_server_trust_chain(&chain, &trust_store, &server, time).is_ok());
+
+        let server = ServerVerification::new("127.0.0.1").unwrap();
+        assert!(certificate.verify_server_trust_chain(&chain, &trust_store, &server, time).is_ok());
+
+        let server = ServerVerification::new("other.certain.test").unwrap();
+        assert!(certificate.verify_server_trust_chain(&chain, &trust_store, &server, time).is_err());
+    }
+
+    #[test]
+    fn verify_server_trust_chain_client_certificate() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let server = ServerVerification::new("client.certain.test").unwrap();
+        assert!(certificate.verify_server_trust_chain(&chain, &trust_store, &server, time).is_err());
+        assert!(certificate.verify_client_trust_chain(&chain, &trust_store, time).is_ok());
+    }
+
+    #[test]
+    fn verify_client_trust_chain_server_certificate() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        assert!(certificate.verify_client_trust_chain(&chain, &trust_store, time).is_err());
+    }
+
+    #[test]
+    fn server_verification_name() {
+        assert!(ServerVerification::new("www.google.com").is_some());
+        assert!(ServerVerification::new("::1").is_some());
+        assert!(ServerVerification::new("not a name").is_none());
+    }
+
+    #[test]
+    fn verify_server_trust_chain_at_time() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/expired.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+
+        let server = ServerVerification::new("expired.certain.test").unwrap();
+        assert!(certificate.verify_server_trust_chain(&chain, &trust_store, &server, Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()).is_ok());
+        assert!(certificate.verify_server_trust_chain(&chain, &trust_store, &server, Utc.with_ymd_and_hms(2024, 9, 1, 0, 0, 0).unwrap()).is_err());
+        assert!(certificate.verify_server_trust_chain(&chain, &trust_store, &server, Utc.with_ymd_and_hms(2023, 9, 1, 0, 0, 0).unwrap()).is_err());
+    }
+
+    #[test]
+    fn read_certificate_validity_at_time() {
+        let data = include_bytes!("../assets/test/certificate.cer");
+        let certificate = Certificate::parse(data);
+
+        if let Some(certificate) = certificate {
+            assert!(certificate.validity().is_within_valid_time_at(Utc.with_ymd_and_hms(2023, 3, 1, 0, 0, 0).unwrap()));
+            assert!(!certificate.validity().is_within_valid_time_at(Utc.with_ymd_and_hms(2023, 5, 1, 0, 0, 0).unwrap()));
+            assert!(!certificate.validity().is_within_valid_time());
+        }
+    }
+
+    #[test]
+    fn verify_trust_chain_report() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let server = ServerVerification::new("server.certain.test").unwrap();
+        let report = certificate.verify_server_trust_chain(&chain, &trust_store, &server, time).unwrap();
+
+        assert_eq!(report.path().len(), 2);
+        assert_eq!(report.path()[0].subject_name(), Some("server.certain.test"));
+        assert_eq!(report.path()[1].subject_name(), Some("Certain Test Intermediate"));
+        assert_eq!(report.anchor().and_then(|anchor| anchor.subject_name()), Some("Certain Test Root"));
+    }
+
+    #[test]
+    fn verify_trust_chain_error_reason() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/expired.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+
+        let server = ServerVerification::new("expired.certain.test").unwrap();
+        let error = certificate.verify_server_trust_chain(&chain, &trust_store, &server, Utc.with_ymd_and_hms(2024, 9, 1, 0, 0, 0).unwrap()).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::Expired);
+        assert_eq!(error.depth(), Some(0));
+
+        let time = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        let error = certificate.verify_server_trust_chain(&[], &trust_store, &server, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::UnknownIssuer);
+        assert_eq!(error.depth(), Some(0));
+
+        let server = ServerVerification::new("server.certain.test").unwrap();
+        let error = certificate.verify_server_trust_chain(&chain, &trust_store, &server, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::NotValidForName);
+        assert_eq!(error.depth(), Some(0));
+    }
+
+    #[test]
+    fn verify_trust_chain_error_purpose() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let server = ServerVerification::new("client.certain.test").unwrap();
+        let error = certificate.verify_server_trust_chain(&chain, &trust_store, &server, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::InvalidPurpose);
+        assert_eq!(error.depth(), Some(0));
+    }
+
+    #[test]
+    fn trust_store_from_certificates() {
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        let trust_store = TrustStore::from_certificates(&[root]).unwrap();
+        assert_eq!(trust_store.len(), 1);
+        assert_eq!(trust_store.certificates()[0].subject_name(), Some("Certain Test Root"));
+
+        let pending = Certificate::parse(&(include_bytes!("../assets/test/chain/root.cer")[4..])).unwrap();
+        assert!(matches!(pending, Certificate::Pending(..)));
+        let error = TrustStore::from_certificates(&[pending]).unwrap_err();
+        assert_eq!(error.reason(), TrustStoreReason::Anchor);
+        assert_eq!(error.entry(), Some(0));
+        assert_eq!(error.path(), None);
+    }
+
+    #[test]
+    fn trust_store_native_roots() {
+        let root = include_bytes!("../assets/test/chain/root.cer");
+        let native = [
+            rustls_native_certs::Certificate(root.to_vec()),
+            rustls_native_certs::Certificate(vec![0x30, 0x00]),
+            rustls_native_certs::Certificate(root[4..].to_vec()),
+        ];
+
+        let mut anchors = Vec::new();
+        let mut errors = Vec::new();
+        super::store::load_native(&native, &mut anchors, &mut errors);
+
+        assert_eq!(anchors.len(), 1);
+        assert_eq!(errors.iter().map(|error| (error.entry(), error.reason())).collect::<Vec<_>>(), vec![
+            (Some(1), TrustStoreReason::Certificate),
+            (Some(2), TrustStoreReason::Anchor),
+        ]);
+    }
+
+    #[test]
+    fn trust_store_from_pem() {
+        let trust_store = TrustStore::from_pem_file("assets/test/chain/roots.pem").unwrap();
+        assert_eq!(trust_store.len(), 2);
+        let error = TrustStore::from_pem(b"-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n").unwrap_err();
+        assert_eq!(error.reason(), TrustStoreReason::Certificate);
+        assert_eq!(error.entry(), Some(0));
+
+        let error = TrustStore::from_pem_file("assets/test/chain/missing.pem").unwrap_err();
+        assert_eq!(error.reason(), TrustStoreReason::Read);
+        assert!(error.path().unwrap().ends_with("missing.pem"));
+    }
+
+    #[test]
+    fn verify_trust_chain_error_trust_store() {
+        let pending = Certificate::parse(&(include_bytes!("../assets/test/chain/root.cer")[4..])).unwrap();
+        let error = VerificationError::from(TrustStore::from_certificates(&[pending]).unwrap_err());
+        assert_eq!(error.reason(), VerificationReason::TrustStore);
+        assert_eq!(error.depth(), None);
+    }
+
+    #[test]
+    fn trust_store_merge() {
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        let trust_store = TrustStore::from_certificates(&[root]).unwrap();
+
+        let web_roots = TrustStore::web_roots();
+        assert!(!web_roots.is_empty());
+
+        let merged = web_roots.merge(&trust_store).merge(&trust_store);
+        assert_eq!(merged.len(), web_roots.len() + 1);
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let server = ServerVerification::new("server.certain.test").unwrap();
+        assert!(certificate.verify_server_trust_chain(&chain, &web_roots, &server, time).is_err());
+        assert!(certificate.verify_server_trust_chain(&chain, &merged, &server, time).is_ok());
+    }
+
+    #[test]
+    fn trust_store_shared() {
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        let trust_store = TrustStore::from_certificates(&[root]).unwrap();
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let trust_store = trust_store.clone();
+            let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+            std::thread::spawn(move || {
+                let certificate = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+                let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+                certificate.verify_client_trust_chain(&chain, &trust_store, time).is_ok()
+            })
+        }).collect();
+
+        for handle in handles {
+            assert!(handle.join().unwrap());
+        }
+    }
+
+    #[test]
+    fn read_certificate_subject_hash() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/certificate.cer")).unwrap();
+        assert_eq!(certificate.subject_hash(), Some(0xdcb02fe2));
+        assert_eq!(certificate.issuer_hash(), Some(0xc06d5c68));
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        assert_eq!(certificate.subject_hash(), Some(0xc3706da0));
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/canonical.cer")).unwrap();
+        assert_eq!(certificate.subject_hash(), Some(0xe71f8f9c));
+    }
+
+    #[test]
+    fn trust_store_from_directory() {
+        let (trust_store, errors) = TrustStore::from_directory("assets/test/hashed");
+        assert_eq!(trust_store.len(), 3);
+        assert_eq!(errors.len(), 2);
+
+        assert!(errors[0].path().unwrap().ends_with("00000000.0"));
+        assert_eq!(errors[0].reason(), TrustStoreReason::Hash);
+        assert!(errors[1].path().unwrap().ends_with("invalid.pem"));
+        assert_eq!(errors[1].reason(), TrustStoreReason::Certificate);
+
+        let (_, errors) = TrustStore::from_directory("assets/test/missing");
+        assert_eq!(errors[0].reason(), TrustStoreReason::Read);
+    }
+
+    #[test]
+    fn trust_store_from_bundle_file() {
+        let (trust_store, errors) = TrustStore::from_bundle_file("assets/test/bundle.crt");
+        assert_eq!(trust_store.len(), 2);
+        assert_eq!(errors.len(), 2);
+
+        assert_eq!(errors[0].entry(), Some(1));
+        assert_eq!(errors[0].reason(), TrustStoreReason::Certificate);
+        assert_eq!(errors[1].entry(), Some(3));
+        assert_eq!(errors[1].reason(), TrustStoreReason::Unsupported);
+    }
+
+    #[test]
+    fn build_server_trust_paths() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let cross = Certificate::parse(include_bytes!("../assets/test/chain/cross.cer")).unwrap();
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        let legacy = Certificate::parse(include_bytes!("../assets/test/chain/legacy.cer")).unwrap();
+        let trust_store = TrustStore::from_certificates(&[legacy, root]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let pool = vec![cross, client, intermediate.clone(), intermediate];
+        let server = ServerVerification::new("server.certain.test").unwrap();
+        let reports = certificate.build_server_trust_paths(&pool, &trust_store, &server, time);
+        assert_eq!(reports.len(), 2);
+
+        assert_eq!(reports[0].path().len(), 2);
+        assert_eq!(reports[0].anchor().and_then(|anchor| anchor.subject_name()), Some("Certain Test Root"));
+
+        assert_eq!(reports[1].path().len(), 3);
+        assert_eq!(reports[1].path()[2].issuer_name(), Some("Certain Test Legacy Root"));
+        assert_eq!(reports[1].anchor().and_then(|anchor| anchor.subject_name()), Some("Certain Test Legacy Root"));
+    }
+
+    #[test]
+    fn build_client_trust_paths() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let cross = Certificate::parse(include_bytes!("../assets/test/chain/cross.cer")).unwrap();
+
+        let legacy = Certificate::parse(include_bytes!("../assets/test/chain/legacy.cer")).unwrap();
+        let trust_store = TrustStore::from_certificates(&[legacy]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let reports = certificate.build_client_trust_paths(&[cross.clone(), intermediate.clone()], &trust_store, time);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].path().len(), 3);
+
+        assert!(certificate.build_client_trust_paths(&[intermediate], &trust_store, time).is_empty());
+        assert!(certificate.build_client_trust_paths(&[cross], &trust_store, time).is_empty());
+    }
+
+    #[test]
+    fn verify_signed_by() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+
+        assert_eq!(certificate.verify_signed_by(&intermediate), Ok(()));
+        assert_eq!(intermediate.verify_signed_by(&root), Ok(()));
+        assert_eq!(root.verify_signed_by(&root), Ok(()));
+
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        assert_eq!(client.verify_signed_by(&certificate), Err(SignatureError::BadSignature));
+
+        assert_eq!(certificate.verify_signed_by(&root), Err(SignatureError::AlgorithmMismatch));
+        assert_eq!(intermediate.verify_signed_by(&intermediate), Err(SignatureError::AlgorithmMismatch));
+        assert_eq!(root.verify_signed_by(&intermediate), Err(SignatureError::AlgorithmMismatch));
+    }
+
+    #[test]
+    fn verify_signed_by_algorithms() {
+        for data in [
+            include_bytes!("../assets/test/signature/rsa.cer").as_slice(),
+            include_bytes!("../assets/test/signature/rsa-pss.cer").as_slice(),
+            include_bytes!("../assets/test/signature/p256.cer").as_slice(),
+            include_bytes!("../assets/test/signature/p384.cer").as_slice(),
+            include_bytes!("../assets/test/signature/p521.cer").as_slice(),
+            include_bytes!("../assets/test/signature/ed25519.cer").as_slice(),
+        ] {
+            let certificate = Certificate::parse(data).unwrap();
+            assert_eq!(certificate.verify_signed_by(&certificate), Ok(()), "{:?}", certificate.subject_name());
+
+            let other = Certificate::parse(include_bytes!("../assets/test/signature/p521.cer")).unwrap();
+            if certificate.subject_name() != other.subject_name() {
+                assert!(certificate.verify_signed_by(&other).is_err());
+            }
+        }
+
+        let rsa = Certificate::parse(include_bytes!("../assets/test/signature/rsa.cer")).unwrap();
+        let p256 = Certificate::parse(include_bytes!("../assets/test/signature/p256.cer")).unwrap();
+        let ed25519 = Certificate::parse(include_bytes!("../assets/test/signature/ed25519.cer")).unwrap();
+        assert_eq!(rsa.verify_signed_by(&p256), Err(SignatureError::AlgorithmMismatch));
+        assert_eq!(p256.verify_signed_by(&ed25519), Err(SignatureError::AlgorithmMismatch));
+        assert_eq!(ed25519.verify_signed_by(&rsa), Err(SignatureError::AlgorithmMismatch));
+    }
+
+    #[test]
+    fn verify_signature_by_pending() {
+        let data = include_bytes!("../assets/test/chain/server.cer");
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+
+        let (tbs, _, signature) = super::signature::split_signed(data).unwrap();
+        let pending = Certificate::parse(tbs).unwrap();
+        assert!(matches!(pending, Certificate::Pending(..)));
+
+        assert_eq!(pending.verify_signature_by(&intermediate, signature), Ok(()));
+        assert_eq!(pending.verify_signed_by(&intermediate), Err(SignatureError::Unsigned));
+
+        let mut tampered = signature.to_vec();
+        tampered[10] ^= 0x01;
+        assert!(pending.verify_signature_by(&intermediate, tampered.as_slice()).is_err());
+    }
+
+    #[test]
+    fn verify_trust_chain_error_signature() {
+        let mut data = include_bytes!("../assets/test/chain/intermediate.cer").to_vec();
+        let length = data.len();
+        data[length - 8] ^= 0x01;
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let chain = vec![Certificate::parse(data.as_slice()).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let error = certificate.verify_client_trust_chain(&chain, &trust_store, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::BadSignature);
+        assert_eq!(error.depth(), Some(1));
+    }
+
+    #[test]
+    fn read_certificate_precertificate() {
+        let precertificate = Certificate::parse(include_bytes!("../assets/test/transparency/precert.cer")).unwrap();
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+
+        assert!(precertificate.is_precertificate());
+        assert!(!certificate.is_precertificate());
+
+        let (tbs, _, _) = super::signature::split_signed(precertificate.raw_der_encoded()).unwrap();
+        assert!(!Certificate::parse(tbs).unwrap().is_precertificate());
+
+        let key = SigningKey::generate(KeyAlgorithm::EcdsaP256).unwrap();
+        let validity = CertificateValidity::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(), Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap());
+
+        for critical in [true, false] {
+            let poisoned = CertificateBuilder::new(CertificateName::new("poisoned.certain.test"), validity)
+                .with_extension(CertificateExtension::new("1.3.6.1.4.1.11129.2.4.3", critical, &[0x05, 0x00]))
+                .sign(&key).unwrap();
+
+            assert_eq!(poisoned.is_precertificate(), critical);
+        }
+    }
+
+    #[test]
+    fn read_certificate_final_tbs() {
+        let precertificate = Certificate::parse(include_bytes!("../assets/test/transparency/precert.cer")).unwrap();
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let expected = include_bytes!("../assets/test/transparency/final-tbs.der");
+
+        assert_eq!(precertificate.final_tbs_der_encoded().unwrap(), expected.to_vec());
+        assert_eq!(certificate.final_tbs_der_encoded().unwrap(), expected.to_vec());
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/certificate.cer")).unwrap();
+        let tbs = certificate.final_tbs_der_encoded().unwrap();
+        let pending = Certificate::parse(tbs.as_slice()).unwrap();
+
+        assert!(matches!(pending, Certificate::Pending(..)));
+        assert_eq!(pending.subject_name(), certificate.subject_name());
+        assert_eq!(pending.final_tbs_der_encoded().unwrap(), tbs);
+        assert!(tbs.len() < certificate.raw_der_encoded().len());
+    }
+
+    #[test]
+    fn read_certificate_signed_timestamps() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/certificate.cer")).unwrap();
+        let timestamps = certificate.signed_timestamps().unwrap();
+
+        assert_eq!(timestamps.len(), 2);
+        assert_eq!(timestamps[0].log_id()[..4], [0xAD, 0xF7, 0xBE, 0xFA]);
+        assert_eq!(timestamps[1].log_id()[..4], [0x7A, 0x32, 0x8C, 0x54]);
+        assert_eq!(timestamps[0].time(), Utc.with_ymd_and_hms(2023, 2, 1, 20, 44, 0).unwrap() + chrono::Duration::milliseconds(625));
+        assert_eq!(timestamps[0].hash_algorithm(), TimestampHashAlgorithm::Sha256);
+        assert_eq!(timestamps[0].signature_algorithm(), TimestampSignatureAlgorithm::Ecdsa);
+        assert!(timestamps[0].extensions().is_empty());
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        assert_eq!(certificate.signed_timestamps().unwrap().len(), 3);
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        assert!(certificate.signed_timestamps().is_none());
+    }
+
+    #[test]
+    fn read_signed_timestamp_list() {
+        let data = include_bytes!("../assets/test/transparency/x509.sct");
+        let timestamp = SignedCertificateTimestamp::parse(data).unwrap();
+
+        assert_eq!(timestamp.raw_encoded(), data);
+        assert_eq!(timestamp.digitally_signed_encoded(), &(data[data.len() - timestamp.signature().len() - 4..]));
+        assert!(SignedCertificateTimestamp::parse(&(data[..data.len() - 1])).is_none());
+
+        let mut list = vec![0, 0, (data.len() >> 8) as u8, data.len() as u8];
+        list.extend_from_slice(data);
+        list.extend_from_slice(&[0, 3, 1, 2, 3]);
+
+        let length = list.len() - 2;
+        list[0] = (length >> 8) as u8;
+        list[1] = length as u8;
+
+        let timestamps = SignedCertificateTimestamp::parse_list(list.as_slice()).unwrap();
+        assert_eq!(timestamps.len(), 2);
+        assert_eq!(timestamps[0], timestamp);
+        assert_eq!(timestamps[0].version(), TimestampVersion::V1);
+        assert_eq!(timestamps[1].version(), TimestampVersion::Other(1));
+        assert_eq!(timestamps[1].raw_encoded(), &[1, 2, 3]);
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let keys = vec![LogKey::new(include_bytes!("../assets/test/transparency/log-a.pub")).unwrap()];
+        let verifications = certificate.verify_delivered_timestamps(timestamps.as_slice(), keys.as_slice());
+
+        assert!(verifications[0].is_valid());
+        assert_eq!(verifications[1].result(), Err(TimestampError::UnsupportedVersion));
+        assert_eq!(timestamps[1].verify_x509_entry(&certificate, &(keys[0])), Err(TimestampError::UnsupportedVersion));
+    }
+
+    #[test]
+    fn verify_embedded_timestamps() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let issuer = Certificate::parse(include_bytes!("../assets/test/transparency/issuer.cer")).unwrap();
+        let keys = vec![
+            LogKey::new(include_bytes!("../assets/test/transparency/log-a.pub")).unwrap(),
+            LogKey::new(include_bytes!("../assets/test/transparency/log-b.pub")).unwrap(),
+            LogKey::new(include_bytes!("../assets/test/transparency/log-c.pub")).unwrap(),
+        ];
+
+        let verifications = certificate.verify_embedded_timestamps(&issuer, keys.as_slice());
+        assert_eq!(verifications.len(), 3);
+        assert!(verifications.iter().all(|verification| verification.is_valid()));
+
+        let verifications = certificate.verify_embedded_timestamps(&issuer, &(keys[..2]));
+        assert_eq!(verifications[2].result(), Err(TimestampError::UnknownLog));
+
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let verifications = certificate.verify_embedded_timestamps(&intermediate, keys.as_slice());
+        assert!(verifications.iter().all(|verification| verification.result() == Err(TimestampError::BadSignature)));
+
+        let precertificate = Certificate::parse(include_bytes!("../assets/test/transparency/precert.cer")).unwrap();
+        let timestamp = &(verifications[0].timestamp());
+        assert_eq!(timestamp.verify_precert_entry(&precertificate, &issuer, &(keys[0])), Ok(()));
+        assert_eq!(timestamp.verify_precert_entry(&precertificate, &issuer, &(keys[1])), Err(TimestampError::UnknownLog));
+    }
+
+    #[test]
+    fn verify_delivered_timestamps() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let precertificate = Certificate::parse(include_bytes!("../assets/test/transparency/precert.cer")).unwrap();
+        let keys = vec![LogKey::new(include_bytes!("../assets/test/transparency/log-a.pub")).unwrap()];
+        let timestamps = vec![SignedCertificateTimestamp::parse(include_bytes!("../assets/test/transparency/x509.sct")).unwrap()];
+
+        let verifications = certificate.verify_delivered_timestamps(timestamps.as_slice(), keys.as_slice());
+        assert!(verifications[0].is_valid());
+
+        let verifications = precertificate.verify_delivered_timestamps(timestamps.as_slice(), keys.as_slice());
+        assert_eq!(verifications[0].result(), Err(TimestampError::BadSignature));
+    }
+
+    #[test]
+    fn read_log_list() {
+        let log_list = CtLogList::from_json(include_bytes!("../assets/test/transparency/log_list.json")).unwrap();
+        assert_eq!(log_list.logs().len(), 5);
+        assert_eq!(log_list.version(), Some("1.0"));
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let names: Vec<String> = certificate.signed_timestamps().unwrap().iter()
+            .map(|timestamp| log_list.find(timestamp.log_id()).unwrap().to_string())
+            .collect();
+
+        assert_eq!(names, vec!["Certain Test Alpha2025", "Certain Other Beta2025", "Certain Test Gamma2025"]);
+
+        let log = log_list.logs().iter().find(|log| log.name() == "Gamma2025").unwrap();
+        assert!(log.is_tiled());
+        assert_eq!(log.url(), "https://ct.certain.test/gamma2025/");
+        assert_eq!(log.state(), Some(CtLogState::Usable));
+        assert!(log.interval().unwrap().contains(Utc.with_ymd_and_hms(2025, 4, 1, 0, 0, 0).unwrap()));
+        assert!(!log.interval().unwrap().contains(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()));
+
+        let log = log_list.logs().iter().find(|log| log.name() == "Delta2024").unwrap();
+        assert_eq!(log.state(), Some(CtLogState::Retired));
+        assert_eq!(log.state_time(), Some(Utc.with_ymd_and_hms(2024, 10, 1, 0, 0, 0).unwrap()));
+
+        let log = log_list.logs().iter().find(|log| log.name() == "Epsilon2024").unwrap();
+        assert_eq!(log.state(), Some(CtLogState::ReadOnly));
+
+        let issuer = Certificate::parse(include_bytes!("../assets/test/transparency/issuer.cer")).unwrap();
+        let verifications = certificate.verify_embedded_timestamps(&issuer, log_list.keys().as_slice());
+        assert!(verifications.iter().all(|verification| verification.is_valid()));
+
+        assert!(CtLogList::from_json(b"{}").is_none());
+    }
+
+    #[test]
+    fn evaluate_ct_policy() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let issuer = Certificate::parse(include_bytes!("../assets/test/transparency/issuer.cer")).unwrap();
+        let log_list = CtLogList::from_json(include_bytes!("../assets/test/transparency/log_list.json")).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap();
+
+        for policy in [CtPolicy::Chrome, CtPolicy::Apple] {
+            let report = certificate.evaluate_ct_policy(&issuer, &log_list, policy, time);
+            assert!(report.is_compliant(), "{}", report);
+            assert!(report.timestamps().iter().all(|(_, exclusion)| exclusion.is_none()));
+        }
+
+        let report = certificate.evaluate_ct_policy(&issuer, &log_list, CtPolicy::Chrome, Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap());
+        assert_eq!(report.timestamps()[0].1, Some(CtTimestampExclusion::FutureTimestamp));
+        assert_eq!(report.failures()[0], CtPolicyFailure::TooFewTimestamps { required: 2, found: 0 });
+
+        let report = certificate.evaluate_ct_policy(&(certificate), &log_list, CtPolicy::Apple, time);
+        assert!(report.timestamps().iter().all(|(_, exclusion)| matches!(exclusion, Some(CtTimestampExclusion::Invalid(..)))));
+        assert!(!report.is_compliant());
+    }
+
+    #[test]
+    fn evaluate_ct_policy_operators() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let issuer = Certificate::parse(include_bytes!("../assets/test/transparency/issuer.cer")).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap();
+
+        let log_list = CtLogList::from_json(include_bytes!("../assets/test/transparency/log_list_retired.json")).unwrap();
+        assert_eq!(log_list.find(certificate.signed_timestamps().unwrap()[0].log_id()).unwrap().state(), Some(CtLogState::Retired));
+
+        let report = certificate.evaluate_ct_policy(&issuer, &log_list, CtPolicy::Chrome, time);
+        assert_eq!(report.timestamps()[0].1, Some(CtTimestampExclusion::Retired));
+        assert!(report.is_compliant(), "{}", report);
+
+        let log_list = CtLogList::from_json(include_bytes!("../assets/test/transparency/log_list_operator.json")).unwrap();
+
+        let report = certificate.evaluate_ct_policy(&issuer, &log_list, CtPolicy::Apple, time);
+        assert_eq!(report.failures(), &[CtPolicyFailure::TooFewOperators { required: 2, found: 1 }]);
+        assert_eq!(report.to_string(), "does not comply with the Apple policy: 2 distinct log operators required, 1 found");
+    }
+
+    #[test]
+    fn evaluate_ct_policy_log_states() {
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let issuer = Certificate::parse(include_bytes!("../assets/test/transparency/issuer.cer")).unwrap();
+        let log_list = CtLogList::from_json(include_bytes!("../assets/test/transparency/log_list_states.json")).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap();
+
+        let report = certificate.evaluate_ct_policy(&issuer, &log_list, CtPolicy::Chrome, time);
+        assert_eq!(report.timestamps()[0].1, None);
+        assert_eq!(report.timestamps()[1].1, Some(CtTimestampExclusion::NotQualified));
+        assert_eq!(report.timestamps()[2].1, Some(CtTimestampExclusion::NotQualified));
+
+        assert_eq!(report.failures(), &[
+            CtPolicyFailure::TooFewTimestamps { required: 2, found: 1 },
+            CtPolicyFailure::TooFewCurrentTimestamps { required: 1, found: 0 },
+            CtPolicyFailure::TooFewOperators { required: 2, found: 1 },
+        ]);
+    }
+
+    #[test]
+    fn read_log_entries() {
+        let entries = LogEntry::from_json(include_bytes!("../assets/test/transparency/get-entries.json")).unwrap();
+        let precertificate = Certificate::parse(include_bytes!("../assets/test/transparency/precert.cer")).unwrap();
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let issuer = Certificate::parse(include_bytes!("../assets/test/transparency/issuer.cer")).unwrap();
+
+        assert_eq!(entries.len(), 2);
+
+        let entry = &(entries[0]);
+        assert_eq!(entry.entry_type(), LogEntryType::Precert);
+        assert_eq!(entry.time(), Utc.with_ymd_and_hms(2025, 1, 1, 0, 5, 0).unwrap());
+        assert!(matches!(entry.certificate(), Certificate::Pending(..)));
+        assert_eq!(entry.certificate().raw_der_encoded(), certificate.final_tbs_der_encoded().unwrap().as_slice());
+        assert_eq!(entry.precertificate().unwrap().raw_der_encoded(), precertificate.raw_der_encoded());
+        assert_eq!(entry.issuer_key_hash().unwrap(), ring::digest::digest(&(ring::digest::SHA256), issuer.public_key_der_encoded().unwrap().as_slice()).as_ref());
+        assert_eq!(entry.chain()[0].raw_der_encoded(), issuer.raw_der_encoded());
+
+        let entry = &(entries[1]);
+        assert_eq!(entry.entry_type(), LogEntryType::X509);
+        assert_eq!(entry.certificate().raw_der_encoded(), certificate.raw_der_encoded());
+        assert!(entry.issuer_key_hash().is_none());
+        assert!(entry.precertificate().is_none());
+        assert_eq!(entry.chain().len(), 1);
+
+        assert!(LogEntry::parse(&(entry.leaf_input()[1..]), &[0, 0, 0]).is_none());
+    }
+
+    #[test]
+    fn read_data_tile() {
+        let leaves = TileLeaf::parse_tile(include_bytes!("../assets/test/transparency/tile/data/000.p/2")).unwrap();
+        let entries = LogEntry::from_json(include_bytes!("../assets/test/transparency/get-entries.json")).unwrap();
+        let issuer = include_bytes!("../assets/test/transparency/issuer.cer");
+
+        assert_eq!(leaves.len(), 2);
+        for (index, (leaf, entry)) in leaves.iter().zip(entries.iter()).enumerate() {
+            assert_eq!(leaf.leaf_index(), Some(index as u64));
+            assert_eq!(leaf.entry_type(), entry.entry_type());
+            assert_eq!(leaf.timestamp(), entry.timestamp());
+            assert_eq!(leaf.certificate().raw_der_encoded(), entry.certificate().raw_der_encoded());
+            assert_eq!(leaf.issuer_key_hash(), entry.issuer_key_hash());
+            assert_eq!(leaf.chain().len(), 1);
+            assert_eq!(leaf.chain()[0].as_slice(), ring::digest::digest(&(ring::digest::SHA256), issuer).as_ref());
+        }
+
+        assert!(leaves[0].precertificate().is_some());
+        assert!(leaves[1].precertificate().is_none());
+        assert!(TileLeaf::parse_tile(&(include_bytes!("../assets/test/transparency/tile/data/000.p/2")[1..])).is_none());
+    }
+
+    #[test]
+    fn verify_checkpoint() {
+        let checkpoint = Checkpoint::parse(include_bytes!("../assets/test/transparency/checkpoint")).unwrap();
+        let key = LogKey::new(include_bytes!("../assets/test/transparency/log-a.pub")).unwrap();
+        let other = LogKey::new(include_bytes!("../assets/test/transparency/log-b.pub")).unwrap();
+
+        assert_eq!(checkpoint.origin(), "ct.certain.test/alpha2025");
+        assert_eq!(checkpoint.size(), 2);
+        assert_eq!(checkpoint.signatures().len(), 1);
+        assert!(checkpoint.extensions().is_empty());
+
+        let time = checkpoint.verify("ct.certain.test/alpha2025", &key).unwrap();
+        assert_eq!(time, Utc.with_ymd_and_hms(2025, 1, 1, 0, 6, 0).unwrap());
+
+        assert_eq!(checkpoint.verify("ct.certain.test/alpha2025", &other), Err(TimestampError::UnknownLog));
+        assert_eq!(checkpoint.verify("ct.certain.test/beta2025", &key), Err(TimestampError::UnknownLog));
+
+        let data = String::from_utf8(include_bytes!("../assets/test/transparency/checkpoint").to_vec()).unwrap();
+        let tampered = Checkpoint::parse(data.replacen("\n2\n", "\n3\n", 1).as_bytes()).unwrap();
+        assert_eq!(tampered.verify("ct.certain.test/alpha2025", &key), Err(TimestampError::BadSignature));
+    }
+
+    #[test]
+    fn verify_inclusion_proof() {
+        for size in 1..=12u8 {
+            let inputs: Vec<Vec<u8>> = (0..size).map(|index| vec![index]).collect();
+            let tree = MerkleTree::from_leaf_inputs(inputs.iter().map(|input| input.as_slice()));
+            let root = tree.root_hash(tree.len()).unwrap();
+
+            for index in 0..tree.len() {
+                let proof = tree.inclusion_proof(index, tree.len()).unwrap();
+                assert!(proof.verify(tree.leaf_hash(index).unwrap(), &(root)));
+                assert!(!proof.verify(&[0; 32], &(root)));
+
+                let proof = InclusionProof::new(index + 1, tree.len(), proof.path().to_vec());
+                assert!(!proof.verify(tree.leaf_hash(index).unwrap(), &(root)));
+            }
+        }
+
+        assert!(MerkleTree::new().inclusion_proof(0, 0).is_none());
+    }
+
+    #[test]
+    fn verify_consistency_proof() {
+        let mut tree = MerkleTree::new();
+        for index in 0..12u8 {
+            tree.push([index; 32]);
+        }
+
+        for second in 1..=tree.len() {
+            let second_root = tree.root_hash(second).unwrap();
+
+            for first in 1..=second {
+                let first_root = tree.root_hash(first).unwrap();
+                let proof = tree.consistency_proof(first, second).unwrap();
+
+                assert!(proof.verify(&(first_root), &(second_root)), "{} {}", first, second);
+                if first < second {
+                    assert!(!proof.verify(&(second_root), &(second_root)));
+                }
+            }
+        }
+
+        let proof = ConsistencyProof::from_json(br#"{"consistency": []}"#, 2, 2).unwrap();
+        assert!(proof.path().is_empty());
+    }
+
+    #[test]
+    fn verify_signed_tree_head() {
+        let head = SignedTreeHead::from_json(include_bytes!("../assets/test/transparency/get-sth.json")).unwrap();
+        let checkpoint = Checkpoint::parse(include_bytes!("../assets/test/transparency/checkpoint")).unwrap();
+        let key = LogKey::new(include_bytes!("../assets/test/transparency/log-a.pub")).unwrap();
+        let other = LogKey::new(include_bytes!("../assets/test/transparency/log-b.pub")).unwrap();
+
+        assert_eq!(head.verify(&key), Ok(()));
+        assert_eq!(head.verify(&other), Err(TimestampError::BadSignature));
+        assert_eq!(head.root_hash(), checkpoint.root_hash());
+
+        let entries = LogEntry::from_json(include_bytes!("../assets/test/transparency/get-entries.json")).unwrap();
+        let leaves = TileLeaf::parse_tile(include_bytes!("../assets/test/transparency/tile/data/000.p/2")).unwrap();
+
+        let tree = MerkleTree::from_leaf_inputs(leaves.iter().map(|leaf| leaf.leaf_input()));
+        assert_eq!(tree.root_hash(head.size()).unwrap(), head.root_hash());
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.inclusion_proof(index as u64, head.size()).unwrap();
+            assert!(proof.verify(&(leaf.leaf_hash()), head.root_hash()));
+        }
+
+        let proof = tree.inclusion_proof(0, head.size()).unwrap();
+        assert!(!proof.verify(&(entries[0].leaf_hash()), head.root_hash()));
+    }
+
+    #[test]
+    fn read_certificate_issuance() {
+        let precertificate = Certificate::parse(include_bytes!("../assets/test/transparency/precert.cer")).unwrap();
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let pending = Certificate::parse(include_bytes!("../assets/test/transparency/final-tbs.der")).unwrap();
+        let other = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+
+        assert!(certificate.is_same_issuance(&precertificate));
+        assert!(precertificate.is_same_issuance(&pending));
+        assert!(pending.is_same_issuance(&certificate));
+        assert!(!certificate.is_same_issuance(&other));
+
+        let id = certificate.issuance_id().unwrap();
+        assert_eq!(id.len(), 64);
+        assert_eq!(precertificate.issuance_id().unwrap(), id);
+        assert_eq!(pending.issuance_id().unwrap(), id);
+        assert_ne!(other.issuance_id().unwrap(), id);
+    }
+
+    #[test]
+    fn sign_with_signing_key() {
+        let keys = vec![
+            SigningKey::from_pkcs8(include_bytes!("../assets/test/signature/rsa.key")).unwrap(),
+            SigningKey::from_pkcs8(include_bytes!("../assets/test/signature/p384.key")).unwrap(),
+            SigningKey::generate(KeyAlgorithm::EcdsaP256).unwrap(),
+            SigningKey::generate(KeyAlgorithm::Ed25519).unwrap(),
+        ];
+
+        assert_eq!(keys[0].algorithm(), KeyAlgorithm::Rsa);
+        assert_eq!(keys[1].algorithm(), KeyAlgorithm::EcdsaP384);
+        assert!(SigningKey::generate(KeyAlgorithm::Rsa).is_none());
+
+        let certificate = Certificate::parse(include_bytes!("../assets/test/signature/rsa.cer")).unwrap();
+        assert_eq!(keys[0].public_key_der_encoded(), certificate.public_key_der_encoded().unwrap());
+
+        for key in keys {
+            let signature = key.sign(b"message").unwrap();
+            let public_key = key.public_key_der_encoded();
+            let algorithm = key.algorithm_der_encoded();
+
+            assert_eq!(super::signature::verify(public_key.as_slice(), algorithm.as_slice(), b"message", signature.as_slice()), Ok(()));
+            assert!(super::signature::verify(public_key.as_slice(), algorithm.as_slice(), b"other", signature.as_slice()).is_err());
+            assert_eq!(SigningKey::from_pkcs8(key.pkcs8_der_encoded()).unwrap().public_key_der_encoded(), public_key);
+        }
+    }
+
+    #[test]
+    fn memory_log_submission() {
+        let precertificate = Certificate::parse(include_bytes!("../assets/test/transparency/precert.cer")).unwrap();
+        let certificate = Certificate::parse(include_bytes!("../assets/test/transparency/final.cer")).unwrap();
+        let issuer = Certificate::parse(include_bytes!("../assets/test/transparency/issuer.cer")).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let log = MemoryLog::new(SigningKey::generate(KeyAlgorithm::EcdsaP256).unwrap()).unwrap();
+        assert!(log.is_empty());
+
+        let timestamp = log.add_pre_chain(&[precertificate.clone(), issuer.clone()], time).unwrap();
+        assert_eq!(timestamp.verify_precert_entry(&certificate, &issuer, log.log_key()), Ok(()));
+        assert_eq!(timestamp.time(), time);
+
+        let timestamp = log.add_chain(&[certificate.clone(), issuer.clone()], time).unwrap();
+        assert_eq!(timestamp.verify_x509_entry(&certificate, log.log_key()), Ok(()));
+        assert_eq!(log.add_chain(&[certificate.clone(), issuer.clone()], time + chrono::Duration::days(1)).unwrap(), timestamp);
+        assert_eq!(log.len(), 2);
+
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        assert_eq!(log.add_chain(&[], time), Err(MemoryLogError::EmptyChain));
+        assert_eq!(log.add_chain(std::slice::from_ref(&precertificate), time), Err(MemoryLogError::Precertificate));
+        assert_eq!(log.add_pre_chain(&[certificate.clone(), issuer.clone()], time), Err(MemoryLogError::NotPrecertificate));
+        assert_eq!(log.add_pre_chain(std::slice::from_ref(&precertificate), time), Err(MemoryLogError::MissingIssuer));
+        assert_eq!(log.add_chain(&[certificate.clone(), intermediate], time), Err(MemoryLogError::BadChain));
+
+        let first = log.get_sth(time).unwrap();
+        assert_eq!(first.verify(log.log_key()), Ok(()));
+        assert_eq!(first.size(), 2);
+
+        let entries = log.get_entries(0, 10);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].entry_type(), LogEntryType::Precert);
+        assert!(entries[0].certificate().is_same_issuance(&precertificate));
+        assert_eq!(entries[0].precertificate().unwrap().raw_der_encoded(), precertificate.raw_der_encoded());
+        assert_eq!(entries[1].certificate().raw_der_encoded(), certificate.raw_der_encoded());
+        assert_eq!(entries[1].chain()[0].raw_der_encoded(), issuer.raw_der_encoded());
+
+        for (index, entry) in entries.iter().enumerate() {
+            let proof = log.get_proof_by_hash(&(entry.leaf_hash()), first.size()).unwrap();
+            assert_eq!(proof.index(), index as u64);
+            assert!(proof.verify(&(entry.leaf_hash()), first.root_hash()));
+        }
+
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        log.add_chain(&[server], time).unwrap();
+
+        let second = log.get_sth(time).unwrap();
+        let proof = log.get_sth_consistency(first.size(), second.size()).unwrap();
+        assert!(proof.verify(first.root_hash(), second.root_hash()));
+        assert!(log.get_proof_by_hash(&[0; 32], second.size()).is_none());
+    }
+
+    #[test]
+    fn memory_log_http() {
+        use std::io::{Read, Write};
+        use base64::{Engine};
+
+        fn request(server: &super::HttpServer, request: String) -> String {
+            let mut stream = std::net::TcpStream::connect(server.address()).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        }
+
+        fn body(response: &str) -> &str {
+            response.split_once("\r\n\r\n").unwrap().1
+        }
+
+        let certificate = include_bytes!("../assets/test/transparency/final.cer");
+        let issuer = include_bytes!("../assets/test/transparency/issuer.cer");
+
+        let log = MemoryLog::new(SigningKey::from_pkcs8(include_bytes!("../assets/test/transparency/log-a.key")).unwrap()).unwrap();
+        let server = log.serve("127.0.0.1:0").unwrap();
+        assert!(server.url().starts_with("http://127.0.0.1:"));
+
+        let encode = |data: &[u8]| base64::engine::general_purpose::STANDARD.encode(data);
+
+        let submission = format!(r#"{{"chain": ["{}", "{}"]}}"#, encode(certificate), encode(issuer));
+        let response = request(&server, format!("POST /ct/v1/add-chain HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}", submission.len(), submission));
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(body(&response).contains(encode(log.log_key().id()).as_str()));
+
+        let signature = serde_json::from_str::<serde_json::Value>(body(&response)).unwrap()["signature"].as_str().unwrap().to_owned();
+        let signature = base64::engine::general_purpose::STANDARD.decode(signature).unwrap();
+        assert_eq!(&(signature[..2]), &[4, 3]);
+        assert_eq!(u16::from_be_bytes([signature[2], signature[3]]) as usize, signature.len() - 4);
+
+        let response = request(&server, "POST /ct/v1/add-chain HTTP/1.1\r\nContent-Length: 1073741824\r\n\r\n".to_owned());
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+
+        let response = request(&server, format!("GET /ct/v1/get-sth HTTP/1.1\r\nX-Padding: {}", "x".repeat(8192 - 11)));
+        assert!(response.starts_with("HTTP/1.1 431"), "{}", response);
+
+        let response = request(&server, format!("GET /ct/v1/get-sth HTTP/1.1\r\n{}", "X-Padding: x\r\n".repeat(101)));
+        assert!(response.starts_with("HTTP/1.1 431"), "{}", response);
+
+        let response = request(&server, format!("GET /{} HTTP/1.1", "x".repeat(8192 - 14)));
+        assert!(response.starts_with("HTTP/1.1 400"), "{}", response);
+
+        let response = request(&server, "GET /ct/v1/get-sth HTTP/1.1\r\n\r\n".to_owned());
+        let head = SignedTreeHead::from_json(body(&response).as_bytes()).unwrap();
+        assert_eq!(head.verify(&(LogKey::new(include_bytes!("../assets/test/transparency/log-a.pub")).unwrap())), Ok(()));
+        assert_eq!(head.size(), 1);
+
+        let response = request(&server, "GET /ct/v1/get-entries?start=0&end=0 HTTP/1.1\r\n\r\n".to_owned());
+        let entries = LogEntry::from_json(body(&response).as_bytes()).unwrap();
+        assert_eq!(entries[0].certificate().raw_der_encoded(), certificate.as_slice());
+
+        let hash = encode(&(entries[0].leaf_hash())).replace('+', "%2B").replace('/', "%2F").replace('=', "%3D");
+        let response = request(&server, format!("GET /ct/v1/get-proof-by-hash?hash={}&tree_size=1 HTTP/1.1\r\n\r\n", hash));
+        let proof = InclusionProof::from_json(body(&response).as_bytes(), 1).unwrap();
+        assert!(proof.verify(&(entries[0].leaf_hash()), head.root_hash()));
+
+        let response = request(&server, "GET /ct/v1/get-sth-consistency?first=1&second=1 HTTP/1.1\r\n\r\n".to_owned());
+        assert!(ConsistencyProof::from_json(body(&response).as_bytes(), 1, 1).unwrap().path().is_empty());
+
+        let response = request(&server, "GET /ct/v1/unknown HTTP/1.1\r\n\r\n".to_owned());
+        assert!(response.starts_with("HTTP/1.1 404"));
+
+        drop(server);
+
+        // A listener bound to every interface is woken through loopback when dropped.
+        let server = log.serve("0.0.0.0:0").unwrap();
+        let response = request(&server, "GET /ct/v1/get-sth HTTP/1.1\r\n\r\n".to_owned());
+        assert!(response.starts_with("HTTP/1.1 200"));
+    }
+
+    #[test]
+    fn parse_revocation_list() {
+        let list = RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap();
+
+        assert_eq!(list.issuer_name(), Some("Certain Test Intermediate"));
+        assert_eq!(list.this_update(), Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(list.next_update(), Some(Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap()));
+        assert_eq!(list.number(), Some(&([5u8][..])));
+        assert!(!list.is_delta());
+
+        let point = list.distribution_point().unwrap();
+        assert_eq!(point.names()[0].as_str(), "http://crl.certain.test/intermediate.crl");
+        assert!(point.only_user_certificates());
+        assert!(!point.only_authority_certificates());
+
+        let entry = list.find(&[3]).unwrap();
+        assert_eq!(entry.reason(), Some(RevocationReason::KeyCompromise));
+        assert_eq!(entry.revocation_time(), Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap());
+        assert_eq!(entry.invalidity_time(), Some(Utc.with_ymd_and_hms(2024, 11, 20, 0, 0, 0).unwrap()));
+        assert_eq!(list.find(&[5]).unwrap().reason(), Some(RevocationReason::CertificateHold));
+        assert_eq!(list.entries().len(), 2);
+
+        let pem = RevocationList::from_pem(include_bytes!("../assets/test/revocation/intermediate.pem")).unwrap();
+        assert_eq!(pem.raw_der_encoded(), list.raw_der_encoded());
+
+        let delta = RevocationList::parse(include_bytes!("../assets/test/revocation/delta.crl")).unwrap();
+        assert_eq!(delta.delta_indicator(), Some(&([5u8][..])));
+        assert_eq!(delta.number(), Some(&([6u8][..])));
+        assert_eq!(delta.find(&[5]).unwrap().reason(), Some(RevocationReason::RemoveFromCrl));
+    }
+
+    #[test]
+    fn check_certificate_revocation() {
+        let list = RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap();
+
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+
+        let status = server.check_revocation(&list);
+        assert!(status.is_revoked());
+        if let RevocationStatus::Revoked(entry) = status {
+            assert_eq!(entry.serial(), server.serial());
+        }
+
+        assert_eq!(client.check_revocation(&list), RevocationStatus::Good);
+        assert_eq!(intermediate.check_revocation(&list), RevocationStatus::Unknown);
+
+        let root = RevocationList::parse(include_bytes!("../assets/test/revocation/root.crl")).unwrap();
+        assert_eq!(intermediate.check_revocation(&root), RevocationStatus::Good);
+        assert_eq!(server.check_revocation(&root), RevocationStatus::Unknown);
+    }
+
+    #[test]
+    fn verify_revocation_list_signature() {
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+
+        let list = RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap();
+        assert_eq!(list.verify_signed_by(&intermediate), Ok(()));
+        assert_eq!(list.verify_signed_by(&root), Err(RevocationError::UnknownIssuer));
+
+        let time = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        assert_eq!(list.verify_at(&intermediate, time), Ok(()));
+        assert_eq!(list.verify_at(&intermediate, Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap()), Err(RevocationError::Stale));
+        assert_eq!(list.verify_at(&intermediate, Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap()), Err(RevocationError::NotValidYet));
+
+        let root_list = RevocationList::parse(include_bytes!("../assets/test/revocation/root.crl")).unwrap();
+        assert_eq!(root_list.verify_at(&root, time), Ok(()));
+
+        let server_list = RevocationList::parse(include_bytes!("../assets/test/revocation/server.crl")).unwrap();
+        assert_eq!(server_list.verify_signed_by(&server), Err(RevocationError::InvalidKeyUsage));
+
+        let mut tampered = include_bytes!("../assets/test/revocation/root.crl").to_vec();
+        let position = tampered.len() - 8;
+        tampered[position] ^= 0x01;
+        let tampered = RevocationList::parse(tampered.as_slice()).unwrap();
+        assert_eq!(tampered.verify_signed_by(&root), Err(RevocationError::BadSignature));
+    }
+
+    #[test]
+    fn merge_delta_revocation_list() {
+        let base = RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap();
+        let delta = RevocationList::parse(include_bytes!("../assets/test/revocation/delta.crl")).unwrap();
+
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let expired = Certificate::parse(include_bytes!("../assets/test/chain/expired.cer")).unwrap();
+
+        let set = RevocationSet::merge(&base, std::slice::from_ref(&delta)).unwrap();
+        assert_eq!(set.number(), Some(&([6u8][..])));
+        assert_eq!(set.base_number(), Some(&([5u8][..])));
+        assert_eq!(set.next_update(), Some(Utc.with_ymd_and_hms(2025, 1, 11, 0, 0, 0).unwrap()));
+        assert_eq!(set.entries().len(), 2);
+
+        assert!(server.check_revocation_set(&set).is_revoked());
+        assert!(client.check_revocation_set(&set).is_revoked());
+        assert_eq!(expired.check_revocation_set(&set), RevocationStatus::Good);
+        assert!(expired.check_revocation(&base).is_revoked());
+
+        if let RevocationStatus::Revoked(entry) = client.check_revocation_set(&set) {
+            assert_eq!(entry.reason(), Some(RevocationReason::Superseded));
+        }
+
+        assert_eq!(RevocationSet::merge(&delta, &[]).unwrap_err(), RevocationError::BaseMismatch);
+
+        let root = RevocationList::parse(include_bytes!("../assets/test/revocation/root.crl")).unwrap();
+        assert_eq!(RevocationSet::merge(&root, &[delta]).unwrap_err(), RevocationError::BaseMismatch);
+    }
+
+    #[test]
+    fn verify_trust_chain_with_revocation() {
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+
+        let intermediate_list = RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap();
+        let root_list = RevocationList::parse(include_bytes!("../assets/test/revocation/root.crl")).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+
+        let options = RevocationOptions::new(RevocationPolicy::HardFail)
+            .with_revocation_list(intermediate_list.clone())
+            .with_revocation_list(root_list.clone());
+
+        let report = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &options, time).unwrap();
+        assert_eq!(report.revocation(), &[RevocationStatus::Good, RevocationStatus::Good]);
+
+        let name = ServerVerification::new("server.certain.test").unwrap();
+        let error = server.verify_server_trust_chain_with_revocation(&chain, &trust_store, &name, &options, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::Revoked);
+        assert_eq!(error.depth(), Some(0));
+
+        let hard = RevocationOptions::new(RevocationPolicy::HardFail)
+            .with_revocation_list(intermediate_list.clone());
+
+        let error = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &hard, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::RevocationUnknown);
+        assert_eq!(error.depth(), Some(1));
+
+        let soft = RevocationOptions::new(RevocationPolicy::SoftFail)
+            .with_revocation_list(intermediate_list.clone());
+
+        let report = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &soft, time).unwrap();
+        assert_eq!(report.revocation(), &[RevocationStatus::Good, RevocationStatus::Unknown]);
+
+        let leaf = RevocationOptions::new(RevocationPolicy::LeafOnly)
+            .with_revocation_list(intermediate_list.clone());
+
+        assert!(client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &leaf, time).is_ok());
+        assert!(client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &leaf, Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap()).is_err());
+
+        let revoked = RevocationOptions::new(RevocationPolicy::SoftFail)
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/root-revoked.crl")).unwrap());
+
+        let error = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &revoked, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::Revoked);
+        assert_eq!(error.depth(), Some(1));
+
+        let delta = RevocationOptions::new(RevocationPolicy::SoftFail)
+            .with_revocation_list(intermediate_list)
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/delta.crl")).unwrap());
+
+        let time = Utc.with_ymd_and_hms(2025, 1, 10, 12, 0, 0).unwrap();
+        assert!(client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &delta, time).is_err());
+        assert!(client.verify_client_trust_chain(&chain, &trust_store, time).is_ok());
+    }
+
+    #[test]
+    fn verify_trust_chain_with_revocation_key_anchor() {
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        let chain = vec![intermediate.clone()];
+
+        let mut anchor = TrustAnchor::from_certificate(&root).unwrap();
+        anchor.certificate = None;
+
+        let trust_store = TrustStore::from_anchors(vec![anchor]);
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+
+        let options = RevocationOptions::new(RevocationPolicy::HardFail)
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap())
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/root.crl")).unwrap());
+
+        let report = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &options, time).unwrap();
+        assert_eq!(report.revocation(), &[RevocationStatus::Good, RevocationStatus::Good]);
+        assert!(report.anchor().is_none());
+
+        let revoked = RevocationOptions::new(RevocationPolicy::HardFail)
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap())
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/root-revoked.crl")).unwrap());
+
+        let error = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &revoked, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::Revoked);
+        assert_eq!(error.depth(), Some(1));
+
+        let responder = OcspResponder::new(&root, SigningKey::from_pkcs8(include_bytes!("../assets/test/chain/root.key")).unwrap());
+        let response = responder.sign_response(&intermediate, &RevocationStatus::Good, time, time, Some(time + chrono::Duration::days(1))).unwrap();
+
+        let ocsp = RevocationOptions::new(RevocationPolicy::HardFail)
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap())
+            .with_ocsp_response(response);
+
+        let report = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &ocsp, time).unwrap();
+        assert_eq!(report.revocation(), &[RevocationStatus::Good, RevocationStatus::Good]);
+    }
+
+    #[test]
+    fn verify_trust_chain_with_revocation_decoy() {
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        let trust_store = TrustStore::from_certificates(std::slice::from_ref(&root)).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+
+        // Same subject and key identifier as the intermediate, but signed by an unrelated key.
+        let identifier = [&[0x04, 0x14][..], intermediate.subject_key_identifier().unwrap().as_slice()].concat();
+        let validity = CertificateValidity::new(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(), Utc.with_ymd_and_hms(2074, 1, 1, 0, 0, 0).unwrap());
+
+        let key = SigningKey::generate(KeyAlgorithm::EcdsaP256).unwrap();
+        let decoy = CertificateBuilder::new(CertificateName::new("Certain Test Intermediate").with_country("NO").with_organization("Certain Test"), validity)
+            .with_issuer(&root)
+            .with_public_key(key.public_key_der_encoded().as_slice())
+            .with_basic_constraints(true, Some(0))
+            .with_extension(CertificateExtension::new("2.5.29.14", false, identifier.as_slice()))
+            .build(key.algorithm(), None).unwrap()
+            .sign(&key).unwrap();
+
+        assert_eq!(decoy.subject_der_encoded(), intermediate.subject_der_encoded());
+        assert!(super::verification::issued_by(&client, &decoy));
+
+        let chain = vec![decoy, intermediate.clone()];
+        let report = client.verify_client_trust_chain(&chain, &trust_store, time).unwrap();
+        assert_eq!(report.path()[1].raw_der_encoded(), intermediate.raw_der_encoded());
+
+        let revoked = RevocationOptions::new(RevocationPolicy::SoftFail)
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/root-revoked.crl")).unwrap());
+
+        let error = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &revoked, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::Revoked);
+        assert_eq!(error.depth(), Some(1));
+    }
+
+    #[test]
+    fn build_ocsp_request() {
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+
+        let request = OcspRequest::new(&server, &intermediate, OcspHashAlgorithm::Sha1).unwrap();
+        assert_eq!(request.der_encoded(), include_bytes!("../assets/test/ocsp/request-sha1.der"));
+        assert!(request.id().matches(&server, &intermediate));
+        assert!(!request.id().matches(&client, &intermediate));
+
+        let nonce: Vec<u8> = (0..16).collect();
+        let request = OcspRequest::new(&client, &intermediate, OcspHashAlgorithm::Sha256).unwrap()
+            .with_nonce(nonce.as_slice());
+
+        assert_eq!(request.der_encoded(), include_bytes!("../assets/test/ocsp/request-nonce.der"));
+        assert_eq!(request.id().issuer_key_hash().len(), 32);
+    }
+
+    #[test]
+    fn verify_ocsp_response() {
+        let root = Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+
+        let response = OcspResponse::parse(include_bytes!("../assets/test/ocsp/server-revoked.der")).unwrap();
+        assert_eq!(response.status(), OcspResponseStatus::Successful);
+        assert_eq!(response.nonce(), Some((0..16).collect::<Vec<u8>>().as_slice()));
+        assert_eq!(response.verify_at(&intermediate, time), Ok(()));
+        assert_eq!(response.verify_signed_by(&root), Err(OcspError::UnknownResponder));
+
+        let single = response.find(&server, &intermediate).unwrap();
+        assert_eq!(single.this_update(), Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(single.next_update(), Some(Utc.with_ymd_and_hms(2025, 1, 8, 0, 0, 0).unwrap()));
+        assert!(single.is_fresh_at(time));
+        assert!(!single.is_fresh_at(Utc.with_ymd_and_hms(2025, 1, 9, 0, 0, 0).unwrap()));
+
+        if let RevocationStatus::Revoked(entry) = server.check_ocsp_response(&intermediate, &response) {
+            assert_eq!(entry.reason(), Some(RevocationReason::KeyCompromise));
+            assert_eq!(entry.revocation_time(), Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap());
+        }
+
+        else {
+
+            panic!("server certificate is not revoked");
+        }
+
+        assert_eq!(client.check_ocsp_response(&intermediate, &response), RevocationStatus::Unknown);
+
+        let response = OcspResponse::parse(include_bytes!("../assets/test/ocsp/client-good.der")).unwrap();
+        assert_eq!(response.certificates().len(), 1);
+        assert_eq!(response.verify_at(&intermediate, time), Ok(()));
+        assert_eq!(client.check_ocsp_response(&intermediate, &response), RevocationStatus::Good);
+
+        let response = OcspResponse::parse(include_bytes!("../assets/test/ocsp/client-unknown.der")).unwrap();
+        assert_eq!(response.verify_signed_by(&intermediate), Ok(()));
+        assert_eq!(client.check_ocsp_response(&intermediate, &response), RevocationStatus::Unknown);
+
+        let response = OcspResponse::parse(include_bytes!("../assets/test/ocsp/client-undelegated.der")).unwrap();
+        assert_eq!(response.verify_signed_by(&intermediate), Err(OcspError::InvalidResponder));
+
+        let response = OcspResponse::parse(include_bytes!("../assets/test/ocsp/try-later.der")).unwrap();
+        assert_eq!(response.status(), OcspResponseStatus::TryLater);
+        assert_eq!(response.verify_signed_by(&intermediate), Err(OcspError::Unsuccessful(OcspResponseStatus::TryLater)));
+    }
+
+    #[test]
+    fn verify_trust_chain_with_ocsp() {
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let chain = vec![Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+
+        let options = RevocationOptions::new(RevocationPolicy::HardFail)
+            .with_ocsp_response(OcspResponse::parse(include_bytes!("../assets/test/ocsp/client-good.der")).unwrap())
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/root.crl")).unwrap());
+
+        let report = client.verify_client_trust_chain_with_revocation(&chain, &trust_store, &options, time).unwrap();
+        assert_eq!(report.revocation(), &[RevocationStatus::Good, RevocationStatus::Good]);
+
+        let stapled = ServerVerification::new("server.certain.test").unwrap()
+            .with_ocsp_response(include_bytes!("../assets/test/ocsp/server-revoked.der"));
+
+        let options = RevocationOptions::new(RevocationPolicy::LeafOnly);
+        let error = server.verify_server_trust_chain_with_revocation(&chain, &trust_store, &stapled, &options, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::Revoked);
+        assert_eq!(error.depth(), Some(0));
+
+        let responder = OcspResponder::new(&(chain[0]), SigningKey::from_pkcs8(include_bytes!("../assets/test/chain/intermediate.key")).unwrap());
+        responder.set_status(&server, RevocationStatus::Good);
+
+        let request = OcspRequest::new(&server, &(chain[0]), OcspHashAlgorithm::Sha256).unwrap();
+        let good = responder.respond(&request, time).unwrap();
+        assert_eq!(server.check_ocsp_response(&(chain[0]), &good), RevocationStatus::Good);
+
+        let options = RevocationOptions::new(RevocationPolicy::LeafOnly)
+            .with_ocsp_response(good)
+            .with_revocation_list(RevocationList::parse(include_bytes!("../assets/test/revocation/intermediate.crl")).unwrap());
+
+        let name = ServerVerification::new("server.certain.test").unwrap();
+        let error = server.verify_server_trust_chain_with_revocation(&chain, &trust_store, &name, &options, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::Revoked);
+        assert_eq!(error.depth(), Some(0));
+    }
+
+    #[test]
+    fn sign_ocsp_response() {
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let key = SigningKey::from_pkcs8(include_bytes!("../assets/test/chain/intermediate.key")).unwrap();
+
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+        let revoked = RevocationStatus::Revoked(RevokedCertificate::new(server.serial(), Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap(), Some(RevocationReason::KeyCompromise)));
+
+        let responder = OcspResponder::new(&intermediate, key);
+        let response = responder.sign_response(&server, &revoked, time, time, Some(Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap())).unwrap();
+        assert_eq!(response.verify_at(&intermediate, time), Ok(()));
+        assert_eq!(response.produced_at(), Some(time));
+        assert_eq!(server.check_ocsp_response(&intermediate, &response), revoked);
+        assert!(response.find(&server, &intermediate).unwrap().is_fresh_at(time));
+
+        let delegated = OcspResponder::new(&intermediate, SigningKey::from_pkcs8(include_bytes!("../assets/test/ocsp/responder.key")).unwrap())
+            .with_responder_certificate(&(Certificate::parse(include_bytes!("../assets/test/ocsp/responder.cer")).unwrap()));
+
+        let response = delegated.sign_response(&client, &RevocationStatus::Good, time, time, None).unwrap();
+        assert_eq!(response.verify_at(&intermediate, time), Ok(()));
+        assert_eq!(response.certificates().len(), 1);
+        assert_eq!(client.check_ocsp_response(&intermediate, &response), RevocationStatus::Good);
+
+        let request = OcspRequest::parse(include_bytes!("../assets/test/ocsp/request-nonce.der")).unwrap();
+        assert_eq!(request.nonce(), Some((0..16).collect::<Vec<u8>>().as_slice()));
+
+        let response = responder.respond(&request, time).unwrap();
+        assert_eq!(response.nonce(), request.nonce());
+        assert_eq!(client.check_ocsp_response(&intermediate, &response), RevocationStatus::Unknown);
+
+        responder.set_status(&client, RevocationStatus::Good);
+        let response = responder.respond(&request, time).unwrap();
+        assert_eq!(client.check_ocsp_response(&intermediate, &response), RevocationStatus::Good);
+
+        let response = OcspResponse::unsuccessful(OcspResponseStatus::TryLater).unwrap();
+        assert_eq!(response.raw_der_encoded(), include_bytes!("../assets/test/ocsp/try-later.der"));
+    }
+
+    #[test]
+    fn serve_ocsp_responder() {
+        use std::io::{Read, Write};
+
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let key = SigningKey::from_pkcs8(include_bytes!("../assets/test/chain/intermediate.key")).unwrap();
+
+        let responder = OcspResponder::new(&intermediate, key);
+        responder.set_status(&server, RevocationStatus::Revoked(RevokedCertificate::new(server.serial(), Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap(), None)));
+
+        let listener = responder.serve("127.0.0.1:0").unwrap();
+        let request = OcspRequest::new(&server, &intermediate, OcspHashAlgorithm::Sha256).unwrap()
+            .with_nonce(b"nonce").der_encoded();
+
+        let mut stream = std::net::TcpStream::connect(listener.address()).unwrap();
+        stream.write_all(format!("POST / HTTP/1.1\r\nContent-Type: application/ocsp-request\r\nContent-Length: {}\r\n\r\n", request.len()).as_bytes()).unwrap();
+        stream.write_all(request.as_slice()).unwrap();
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+
+        let position = response.windows(4).position(|window| window == b"\r\n\r\n").unwrap();
+        assert!(response.starts_with(b"HTTP/1.1 200"));
+
+        let response = OcspResponse::parse(&(response[position + 4..])).unwrap();
+        assert_eq!(response.verify_at(&intermediate, response.produced_at().unwrap()), Ok(()));
+        assert_eq!(response.nonce(), Some(&(b"nonce"[..])));
+        assert!(server.check_ocsp_response(&intermediate, &response).is_revoked());
+
+        let mut stream = std::net::TcpStream::connect(listener.address()).unwrap();
+        stream.write_all(b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\n\x30\x00").unwrap();
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+
+        let position = response.windows(4).position(|window| window == b"\r\n\r\n").unwrap();
+        let response = OcspResponse::parse(&(response[position + 4..])).unwrap();
+        assert_eq!(response.status(), OcspResponseStatus::MalformedRequest);
+
+        // A `+` in the path is left unescaped, as clients commonly do.
+        let encoded = (0u8..).map(|nonce| {
+            use base64::{Engine};
+
+            let request = OcspRequest::new(&server, &intermediate, OcspHashAlgorithm::Sha256).unwrap()
+                .with_nonce(&[nonce]).der_encoded();
+
+            base64::engine::general_purpose::STANDARD.encode(request)
+        }).find(|encoded| encoded.contains('+')).unwrap();
+
+        let mut stream = std::net::TcpStream::connect(listener.address()).unwrap();
+        stream.write_all(format!("GET /{} HTTP/1.1\r\n\r\n", encoded.replace('/', "%2F")).as_bytes()).unwrap();
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+
+        let position = response.windows(4).position(|window| window == b"\r\n\r\n").unwrap();
+        let response = OcspResponse::parse(&(response[position + 4..])).unwrap();
+        assert!(server.check_ocsp_response(&intermediate, &response).is_revoked());
+    }
+
+    #[test]
+    fn parse_certificate_request() {
+        let request = CertificateRequest::parse(include_bytes!("../assets/test/request/request.csr")).unwrap();
+
+        assert_eq!(request.subject_name(), Some("device.certain.test"));
+        assert_eq!(request.subject_country(), Some("NO"));
+        assert_eq!(request.subject_state(), Some("Oslo"));
+        assert_eq!(request.subject_organization(), Some("Certain Test"));
+        assert_eq!(request.subject_organizational_unit(), Some("Enrolment"));
+        assert_eq!(request.challenge_password(), Some("enrolment-secret"));
+
+        assert_eq!(request.subject_alternate_names(), &[
+            CertificateAlternateName::Hostname("device.certain.test".into()),
+            CertificateAlternateName::Hostname("api.certain.test".into()),
+            CertificateAlternateName::Address("10.0.0.1".into()),
+            CertificateAlternateName::Email("ops@certain.test".into()),
+            CertificateAlternateName::Uri("spiffe://certain.test/device".into()),
+        ]);
+
+        assert_eq!(request.key_usages(), &[CertificateKeyUsage::DigitalSignature, CertificateKeyUsage::KeyEncipherment]);
+        assert_eq!(request.extended_key_usages(), &[CertificateExtendedKeyUsage::ServerAuth, CertificateExtendedKeyUsage::ClientAuth]);
+        assert!(request.extensions().iter().any(|extension| extension.oid() == "2.5.29.15" && extension.critical()));
+
+        let key = SigningKey::from_pkcs8(include_bytes!("../assets/test/request/request.key")).unwrap();
+        assert_eq!(request.public_key_der_encoded(), key.public_key_der_encoded().as_slice());
+
+        let pem = CertificateRequest::from_pem(include_bytes!("../assets/test/request/request.pem")).unwrap();
+        assert_eq!(pem.raw_der_encoded(), request.raw_der_encoded());
+    }
+
+    #[test]
+    fn verify_certificate_request() {
+        for data in [
+            &(include_bytes!("../assets/test/request/request.csr")[..]),
+            &(include_bytes!("../assets/test/request/ed25519.csr")[..]),
+            &(include_bytes!("../assets/test/request/rsa.csr")[..]),
+        ] {
+            let request = CertificateRequest::parse(data).unwrap();
+            assert_eq!(request.verify_signature(), Ok(()));
+        }
+
+        let mut data = include_bytes!("../assets/test/request/request.csr").to_vec();
+        let position = data.windows(6).position(|window| window == b"device").unwrap();
+        data[position] = b'D';
+
+        let request = CertificateRequest::parse(data.as_slice()).unwrap();
+        assert_eq!(request.subject_name(), Some("Device.certain.test"));
+        assert_eq!(request.verify_signature(), Err(SignatureError::BadSignature));
+    }
+
+    #[test]
+    fn build_certificate_request() {
+        let keys = [
+            SigningKey::generate(KeyAlgorithm::EcdsaP256).unwrap(),
+            SigningKey::generate(KeyAlgorithm::Ed25519).unwrap(),
+            SigningKey::from_pkcs8(include_bytes!("../assets/test/signature/rsa.key")).unwrap(),
+        ];
+
+        let subject = CertificateName::new("device.certain.test")
+            .with_country("NO")
+            .with_organization("Certain Test");
+
+        let builder = CertificateRequestBuilder::new(subject)
+            .with_alternate_name(CertificateAlternateName::Hostname("device.certain.test".into()))
+            .with_alternate_name(CertificateAlternateName::Hostname("api.certain.test".into()))
+            .with_alternate_name(CertificateAlternateName::Address("2001:db8::1".into()))
+            .with_alternate_name(CertificateAlternateName::Email("ops@certain.test".into()))
+            .with_key_usage(CertificateKeyUsage::DigitalSignature)
+            .with_key_usage(CertificateKeyUsage::KeyAgreement)
+            .with_extended_key_usage(CertificateExtendedKeyUsage::ClientAuth)
+            .with_extended_key_usage(CertificateExtendedKeyUsage::Other("1.3.6.1.4.1.99999.1".into()))
+            .with_challenge_password("enrolment-secret");
+
+        for key in keys.iter() {
+            let request = builder.sign(key).unwrap();
+            assert_eq!(request.verify_signature(), Ok(()));
+            assert_eq!(request.public_key_der_encoded(), key.public_key_der_encoded().as_slice());
+
+            let request = CertificateRequest::from_pem(request.pem_encoded().as_bytes()).unwrap();
+            assert_eq!(request.subject_name(), Some("device.certain.test"));
+            assert_eq!(request.subject_country(), Some("NO"));
+            assert_eq!(request.subject_organization(), Some("Certain Test"));
+            assert_eq!(request.subject_state(), None);
+            assert_eq!(request.challenge_password(), Some("enrolment-secret"));
+
+            assert_eq!(request.subject_alternate_names(), &[
+                CertificateAlternateName::Hostname("device.certain.test".into()),
+                CertificateAlternateName::Hostname("api.certain.test".into()),
+                CertificateAlternateName::Address("2001:db8::1".into()),
+                CertificateAlternateName::Email("ops@certain.test".into()),
+            ]);
+
+            assert_eq!(request.key_usages(), &[CertificateKeyUsage::DigitalSignature, CertificateKeyUsage::KeyAgreement]);
+            assert_eq!(request.extended_key_usages(), &[
+                CertificateExtendedKeyUsage::ClientAuth,
+                CertificateExtendedKeyUsage::Other("1.3.6.1.4.1.99999.1".into()),
+            ]);
+        }
+
+        let builder = CertificateRequestBuilder::new(CertificateName::new("device.certain.test"))
+            .with_alternate_name(CertificateAlternateName::Directory("CN=device.certain.test".into()));
+
+        assert_eq!(builder.sign(&(keys[0])).err(), Some(BuilderError::UnsupportedName));
+
+        for name in [
+            CertificateAlternateName::Hostname("enhet.sertifisert.tést".into()),
+            CertificateAlternateName::Email("drift@sertifisert.tést".into()),
+            CertificateAlternateName::Uri("https://sertifisert.tést/".into()),
+            CertificateAlternateName::Address("2001:db8::g".into()),
+        ] {
+            let builder = CertificateRequestBuilder::new(CertificateName::new("device.certain.test"))
+                .with_alternate_name(name);
+
+            assert_eq!(builder.sign(&(keys[0])).err(), Some(BuilderError::InvalidName));
+        }
+
+        let builder = CertificateRequestBuilder::new(CertificateName::new("device.certain.test"))
+            .with_extended_key_usage(CertificateExtendedKeyUsage::Other("1.3.6.1.4.1.99999.x".into()));
+
+        assert_eq!(builder.sign(&(keys[0])).err(), Some(BuilderError::InvalidObjectIdentifier));
+    }
+
+    #[test]
+    fn issue_certificate_chain() {
+        let validity = CertificateValidity::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(), Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap());
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+
+        let root_key = SigningKey::generate(KeyAlgorithm::EcdsaP256).unwrap();
+        let root = CertificateBuilder::new(CertificateName::new("Certain Root").with_organization("Certain Test"), validity)
+            .with_serial(&[0x01])
+            .with_basic_constraints(true, Some(1))
+            .with_key_usage(CertificateKeyUsage::KeyCertSign)
+            .with_key_usage(CertificateKeyUsage::CrlSign)
+            .sign(&root_key).unwrap();
+
+        assert!(matches!(root, Certificate::Signed(..)));
+        assert!(root.authority());
+        assert_eq!(root.serial(), &[0x01]);
+        assert_eq!(root.issuer_name(), Some("Certain Root"));
+        assert_eq!(root.verify_signed_by(&root), Ok(()));
+
+        let intermediate_key = SigningKey::generate(KeyAlgorithm::EcdsaP384).unwrap();
+        let pending = CertificateBuilder::new(CertificateName::new("Certain Intermediate"), validity)
+            .with_issuer(&root)
+            .with_public_key(intermediate_key.public_key_der_encoded().as_slice())
+            .with_basic_constraints(true, Some(0))
+            .with_key_usage(CertificateKeyUsage::KeyCertSign)
+            .build(KeyAlgorithm::EcdsaP256, None).unwrap();
+
+        assert!(matches!(pending, Certificate::Pending(..)));
+        assert_eq!(pending.issuer_name(), Some("Certain Root"));
+        assert_eq!(pending.verify_signed_by(&root), Err(SignatureError::Unsigned));
+        assert_eq!(pending.sign(&intermediate_key).err(), Some(SignatureError::AlgorithmMismatch));
+
+        let intermediate = pending.sign(&root_key).unwrap();
+        assert!(matches!(intermediate, Certificate::Signed(..)));
+        assert_eq!(intermediate.verify_signed_by(&root), Ok(()));
+        assert_eq!(intermediate.serial().len(), 16);
+
+        let request = CertificateRequest::parse(include_bytes!("../assets/test/request/request.csr")).unwrap();
+        let server = CertificateBuilder::from_request(&request, validity).unwrap()
+            .with_issuer(&intermediate)
+            .with_basic_constraints(false, None)
+            .with_extension(CertificateExtension::new("1.3.6.1.4.1.99999.1", false, &[0x05, 0x00]))
+            .sign(&intermediate_key).unwrap();
+
+        assert!(!server.authority());
+        assert_eq!(server.subject_name(), Some("device.certain.test"));
+        assert_eq!(server.subject_organizational_unit(), Some("Enrolment"));
+        // The certificate leaves out the alternate name repeating its subject name.
+        assert_eq!(server.subject_alternate_names(), &(request.subject_alternate_names()[1..]));
+        assert_eq!(server.public_key_der_encoded().unwrap(), request.public_key_der_encoded());
+
+        let trust_store = TrustStore::from_certificates(std::slice::from_ref(&root)).unwrap();
+        let chain = [intermediate];
+
+        for name in ["device.certain.test", "api.certain.test", "10.0.0.1"] {
+            let verification = ServerVerification::new(name).unwrap();
+            assert!(server.verify_server_trust_chain(&chain, &trust_store, &verification, time).is_ok());
+        }
+
+        let client = server.verify_client_trust_chain(&chain, &trust_store, time);
+        assert!(client.is_ok());
+
+        let mut tampered = include_bytes!("../assets/test/request/request.csr").to_vec();
+        let position = tampered.windows(6).position(|window| window == b"device").unwrap();
+        tampered[position] = b'D';
+
+        let tampered = CertificateRequest::parse(tampered.as_slice()).unwrap();
+        assert_eq!(CertificateBuilder::from_request(&tampered, validity).err(), Some(SignatureError::BadSignature));
+
+        let builder = CertificateBuilder::new(CertificateName::new("Certain Intermediate"), validity)
+            .with_issuer(&root);
+
+        assert_eq!(builder.build(KeyAlgorithm::EcdsaP256, None).err(), Some(BuilderError::MissingPublicKey));
+        assert_eq!(builder.sign(&root_key).err(), Some(BuilderError::MissingPublicKey));
+
+        let builder = builder.with_public_key(intermediate_key.public_key_der_encoded().as_slice());
+        assert_eq!(builder.sign(&intermediate_key).err(), Some(BuilderError::KeyMismatch));
+        assert!(builder.sign(&root_key).is_ok());
+
+        let self_signed = CertificateBuilder::new(CertificateName::new("Certain Root"), validity)
+            .with_public_key(intermediate_key.public_key_der_encoded().as_slice());
+
+        assert_eq!(self_signed.sign(&root_key).err(), Some(BuilderError::KeyMismatch));
+        assert_eq!(builder.clone().with_alternate_name(CertificateAlternateName::Directory("CN=Certain Intermediate".into())).sign(&root_key).err(), Some(BuilderError::UnsupportedName));
+        assert_eq!(builder.with_extension(CertificateExtension::new("2.5.x", false, &[0x05, 0x00])).sign(&root_key).err(), Some(BuilderError::InvalidObjectIdentifier));
+
+        for (begin, end) in [(1949, 2050), (1950, 2049)] {
+            let validity = CertificateValidity::new(Utc.with_ymd_and_hms(begin, 1, 1, 0, 0, 0).unwrap(), Utc.with_ymd_and_hms(end, 12, 31, 0, 0, 0).unwrap());
+            let certificate = CertificateBuilder::new(CertificateName::new("Certain Root"), validity)
+                .sign(&root_key).unwrap();
+
+            assert_eq!(certificate.validity().time_begin(), validity.time_begin());
+            assert_eq!(certificate.validity().time_end(), validity.time_end());
+        }
+    }
+
+    #[test]
+    fn verify_server_trust_chain_delivered_timestamps() {
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let client = Certificate::parse(include_bytes!("../assets/test/chain/client.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let chain = vec![intermediate.clone()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+
+        let log = MemoryLog::new(SigningKey::generate(KeyAlgorithm::EcdsaP256).unwrap()).unwrap();
+        let timestamp = log.add_chain(&[server.clone(), intermediate.clone()], time).unwrap();
+        let other = log.add_chain(&[client, intermediate], time).unwrap();
+
+        let delivered = ServerVerification::new("server.certain.test").unwrap()
+            .with_signed_timestamp(timestamp.raw_encoded())
+            .with_log_key(log.log_key().clone());
+
+        assert_eq!(delivered.signed_timestamps(), &[timestamp.raw_encoded().to_vec()]);
+        assert!(server.verify_server_trust_chain(&chain, &trust_store, &delivered, time).is_ok());
+
+        for verification in [
+            ServerVerification::new("server.certain.test").unwrap()
+                .with_signed_timestamp(timestamp.raw_encoded()),
+            ServerVerification::new("server.certain.test").unwrap()
+                .with_signed_timestamp(other.raw_encoded())
+                .with_log_key(log.log_key().clone()),
+            ServerVerification::new("server.certain.test").unwrap()
+                .with_signed_timestamp(&[0x00])
+                .with_log_key(log.log_key().clone()),
+        ] {
+            let error = server.verify_server_trust_chain(&chain, &trust_store, &verification, time).unwrap_err();
+            assert_eq!(error.reason(), VerificationReason::InvalidSignedTimestamp);
+            assert_eq!(error.depth(), Some(0));
+        }
+    }
+
+    #[test]
+    fn verify_server_trust_chain_stapled_ocsp() {
+        let server = Certificate::parse(include_bytes!("../assets/test/chain/server.cer")).unwrap();
+        let intermediate = Certificate::parse(include_bytes!("../assets/test/chain/intermediate.cer")).unwrap();
+        let chain = vec![intermediate.clone()];
+        let trust_store = TrustStore::from_certificates(&[Certificate::parse(include_bytes!("../assets/test/chain/root.cer")).unwrap()]).unwrap();
+        let time = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+
+        let key = SigningKey::from_pkcs8(include_bytes!("../assets/test/chain/intermediate.key")).unwrap();
+        let good = OcspResponder::new(&intermediate, key)
+            .sign_response(&server, &RevocationStatus::Good, time, time, Some(Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap())).unwrap();
+
+        let stapled = ServerVerification::new("server.certain.test").unwrap()
+            .with_ocsp_response(good.raw_der_encoded());
+
+        assert!(server.verify_server_trust_chain(&chain, &trust_store, &stapled, time).is_ok());
+
+        let error = server.verify_server_trust_chain(&chain, &trust_store, &stapled, Utc.with_ymd_and_hms(2025, 1, 7, 0, 0, 0).unwrap()).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::InvalidOcspResponse);
+        assert_eq!(error.depth(), Some(0));
+
+        let revoked = ServerVerification::new("server.certain.test").unwrap()
+            .with_ocsp_response(include_bytes!("../assets/test/ocsp/server-revoked.der"));
+
+        let error = server.verify_server_trust_chain(&chain, &trust_store, &revoked, time).unwrap_err();
+        assert_eq!(error.reason(), VerificationReason::Revoked);
+        assert_eq!(error.depth(), Some(0));
+
+        for response in [&(include_bytes!("../assets/test/ocsp/client-good.der")[..]), &[0x30, 0x00]] {
+            let other = ServerVerification::new("server.certain.test").unwrap()
+                .with_ocsp_response(response);
+
+            let error = server.verify_server_trust_chain(&chain, &trust_store, &other, time).unwrap_err();
+            assert_eq!(error.reason(), VerificationReason::InvalidOcspResponse);
+        }
+    }
 }
@@ -0,0 +1,146 @@
+use std::{
+
+    collections::{BTreeMap},
+    cmp::{Reverse},
+};
+
+use chrono::{
+
+    DateTime,
+    Utc,
+};
+
+use crate::{
+
+    certificate::{Certificate},
+
+    verification::{
+
+        VerificationReport,
+        Purpose,
+        self,
+    },
+
+    store::{TrustStore},
+};
+
+/// Intermediates a single path may pass through.
+const MAXIMUM_DEPTH: usize = 6;
+
+/// Candidate paths considered before the search gives up.
+const MAXIMUM_CANDIDATES: usize = 64;
+
+/// Partial paths visited before the search gives up, bounding the work on pools of
+/// certificates that issue each other without reaching an anchor.
+const MAXIMUM_VISITS: usize = 1024;
+
+fn is_same_identity(certificate: &Certificate, other: &Certificate) -> bool {
+    certificate.subject_der_encoded() == other.subject_der_encoded() &&
+        certificate.public_key_der_encoded() == other.public_key_der_encoded()
+}
+
+fn search(path: &mut Vec<Certificate>, pool: &[&Certificate], trust_store: &TrustStore, candidates: &mut Vec<(Vec<Certificate>, usize)>, visits: &mut usize) {
+    if candidates.len() >= MAXIMUM_CANDIDATES || *visits >= MAXIMUM_VISITS {
+        return
+    }
+
+    *visits += 1;
+
+    let current = match path.last() {
+        Some(current) => current.clone(),
+        None => return,
+    };
+
+    let issuer = current.issuer_der_encoded().unwrap_or_default();
+
+    for (index, anchor) in trust_store.anchors().iter().enumerate().filter(|(_, anchor)| anchor.is_named(issuer.as_slice())) {
+        if let Some(ref root) = anchor.certificate {
+            if !verification::issued_by(&current, root) {
+                continue
+            }
+        }
+
+        if candidates.len() >= MAXIMUM_CANDIDATES {
+            return
+        }
+
+        candidates.push((path.clone(), index));
+    }
+
+    if path.len() > MAXIMUM_DEPTH {
+        return
+    }
+
+    for candidate in pool.iter() {
+        if !verification::issued_by(&current, candidate) {
+            continue
+        }
+
+        if path.iter().any(|certificate| is_same_identity(certificate, candidate)) {
+            continue
+        }
+
+        path.push((*candidate).clone());
+        search(path, pool, trust_store, candidates, visits);
+        path.pop();
+    }
+}
+
+/// Search all paths from the certificate through the pool to the trust store, keeping
+/// those whose signatures check out link by link and that verify. Shorter paths are
+/// preferred, then those that stay valid the longest.
+pub(crate) fn build(certificate: &Certificate, pool: &[Certificate], trust_store: &TrustStore, purpose: &Purpose<'_>, time: DateTime<Utc>) -> Vec<VerificationReport> {
+    let mut unique: Vec<&Certificate> = Vec::with_capacity(pool.len());
+
+    for candidate in pool.iter() {
+        if candidate.raw_der_encoded() == certificate.raw_der_encoded() {
+            continue
+        }
+
+        if unique.iter().any(|existing| existing.raw_der_encoded() == candidate.raw_der_encoded()) {
+            continue
+        }
+
+        unique.push(candidate);
+    }
+
+    let mut candidates = Vec::new();
+    search(&mut vec![certificate.clone()], unique.as_slice(), trust_store, &mut candidates, &mut 0);
+
+    // Each candidate is verified against its anchor alone, with one store per anchor.
+    let anchors = trust_store.anchors();
+    let mut stores: BTreeMap<usize, TrustStore> = BTreeMap::new();
+
+    for (_, index) in candidates.iter() {
+        stores.entry(*index)
+            .or_insert_with(|| TrustStore::from_anchors(vec![anchors[*index].clone()]));
+    }
+
+    let mut reports: Vec<VerificationReport> = candidates.into_iter().filter_map(|(path, index)| {
+        let anchor = &(anchors[index]);
+
+        let signed = path.iter().enumerate().all(|(depth, certificate)| {
+            match path.get(depth + 1).or(anchor.certificate.as_ref()) {
+                Some(issuer) => certificate.verify_signed_by(issuer).is_ok(),
+                None => true,
+            }
+        });
+
+        if !signed {
+            return None
+        }
+
+        verification::check(&(stores[&index]), &(path[0]), &(path[1..]), purpose, time).ok()?;
+        Some(VerificationReport::new(path, anchor.clone()))
+    }).collect();
+
+    reports.sort_by_key(|report| {
+        let end = report.path().iter()
+            .map(|certificate| certificate.validity().time_end())
+            .min();
+
+        (report.path().len(), Reverse(end))
+    });
+
+    reports
+}
@@ -0,0 +1,345 @@
+use x509_parser::{
+
+    certification_request::{X509CertificationRequest},
+    cri_attributes::{ParsedCriAttribute},
+    extensions::{ParsedExtension},
+    prelude::{FromDer},
+    pem::{Pem},
+};
+
+use crate::{
+
+    certificate::{
+
+        CertificateExtendedKeyUsage,
+        CertificateAlternateName,
+        CertificateKeyUsage,
+        CertificateExtension,
+        CertificateName,
+        self,
+    },
+
+    signature::{
+
+        SignatureError,
+        SigningKey,
+        self,
+    },
+
+    builder::{BuilderError},
+    encoding::{self},
+};
+
+/// Object identifier of the `challengePassword` attribute.
+const CHALLENGE_PASSWORD_OID: &str = "1.2.840.113549.1.9.7";
+
+/// Object identifier of the `extensionRequest` attribute.
+const EXTENSION_REQUEST_OID: &str = "1.2.840.113549.1.9.14";
+
+/// `PKCS#10` certificate signing request, as defined in RFC 2986.
+#[derive(Clone, Debug)]
+pub struct CertificateRequest {
+    subject_name: Option<String>,
+    subject_alternate_names: Vec<CertificateAlternateName>,
+    subject_country: Option<String>,
+    subject_state: Option<String>,
+    subject_organization: Option<String>,
+    subject_organizational_unit: Option<String>,
+    key_usages: Vec<CertificateKeyUsage>,
+    extended_key_usages: Vec<CertificateExtendedKeyUsage>,
+    extensions: Vec<CertificateExtension>,
+    challenge_password: Option<String>,
+    public_key: Vec<u8>,
+    raw_der_encoded: Vec<u8>,
+}
+
+impl CertificateRequest {
+    /// Parse a request from `DER` encoded data.
+    pub fn parse(data: &[u8]) -> Option<CertificateRequest> {
+        let (remaining, request) = X509CertificationRequest::from_der(data).ok()?;
+        let info = &(request.certification_request_info);
+
+        let subject_name = info.subject.iter_common_name()
+            .filter_map(|name| name.as_str().ok())
+            .next().map(|name| name.to_owned());
+
+        let subject_country = info.subject.iter_country()
+            .filter_map(|name| name.as_str().ok())
+            .next().map(|name| name.to_owned());
+
+        let subject_state = info.subject.iter_state_or_province()
+            .filter_map(|name| name.as_str().ok())
+            .next().map(|name| name.to_owned());
+
+        let subject_organization = info.subject.iter_organization()
+            .filter_map(|name| name.as_str().ok())
+            .next().map(|name| name.to_owned());
+
+        let subject_organizational_unit = info.subject.iter_organizational_unit()
+            .filter_map(|name| name.as_str().ok())
+            .next().map(|name| name.to_owned());
+
+        let mut subject_alternate_names = Vec::new();
+        let mut key_usages = Vec::new();
+        let mut extended_key_usages = Vec::new();
+        let mut extensions = Vec::new();
+        let mut challenge_password = None;
+
+        for attribute in info.iter_attributes() {
+            match attribute.parsed_attribute() {
+                ParsedCriAttribute::ExtensionRequest(request) => {
+                    for extension in request.extensions.iter() {
+                        match extension.parsed_extension() {
+                            ParsedExtension::SubjectAlternativeName(names) => {
+                                subject_alternate_names = certificate::alternate_names(names.general_names.as_slice(), None);
+                            },
+                            ParsedExtension::KeyUsage(usage) => {
+                                key_usages = certificate::key_usages(usage);
+                            },
+                            ParsedExtension::ExtendedKeyUsage(usage) => {
+                                extended_key_usages = certificate::extended_key_usages(usage);
+                            },
+                            _ => {},
+                        }
+
+                        extensions.push(CertificateExtension::new(extension.oid.to_id_string().as_str(),
+                            extension.critical, extension.value));
+                    }
+                },
+                ParsedCriAttribute::ChallengePassword(password) => {
+                    challenge_password = Some(password.0.clone());
+                },
+                _ => {},
+            }
+        }
+
+        Some(CertificateRequest {
+            subject_name,
+            subject_alternate_names,
+            subject_country,
+            subject_state,
+            subject_organization,
+            subject_organizational_unit,
+            key_usages,
+            extended_key_usages,
+            extensions,
+            challenge_password,
+            public_key: info.subject_pki.raw.to_vec(),
+            raw_der_encoded: data[..(data.len() - remaining.len())].to_vec(),
+        })
+    }
+
+    /// Parse the first request in `PEM` encoded data.
+    pub fn from_pem(data: &[u8]) -> Option<CertificateRequest> {
+        let pem = Pem::iter_from_buffer(data).flatten()
+            .find(|pem| pem.label == "CERTIFICATE REQUEST" || pem.label == "NEW CERTIFICATE REQUEST")?;
+
+        CertificateRequest::parse(pem.contents.as_slice())
+    }
+
+    /// Verify the self-signature of the request, proving possession of the private key.
+    pub fn verify_signature(&self) -> Result<(), SignatureError> {
+        let (info, algorithm, signature) = signature::split_signed(self.raw_der_encoded())
+            .ok_or(SignatureError::BadEncoding)?;
+
+        signature::verify(self.public_key.as_slice(), algorithm, info, signature)
+    }
+
+    /// Subject of the requested certificate.
+    pub fn subject(&self) -> CertificateName {
+        CertificateName {
+            name: self.subject_name.clone(),
+            country: self.subject_country.clone(),
+            state: self.subject_state.clone(),
+            organization: self.subject_organization.clone(),
+            organizational_unit: self.subject_organizational_unit.clone(),
+        }
+    }
+
+    pub fn subject_name(&self) -> Option<&str> {
+        if let Some(ref subject_name) = self.subject_name {
+            return Some(subject_name.as_str())
+        }
+
+        None
+    }
+
+    pub fn subject_alternate_names(&self) -> &[CertificateAlternateName] {
+        self.subject_alternate_names.as_slice()
+    }
+
+    pub fn subject_country(&self) -> Option<&str> {
+        if let Some(ref subject_country) = self.subject_country {
+            return Some(subject_country.as_str())
+        }
+
+        None
+    }
+
+    pub fn subject_state(&self) -> Option<&str> {
+        if let Some(ref subject_state) = self.subject_state {
+            return Some(subject_state.as_str())
+        }
+
+        None
+    }
+
+    pub fn subject_organization(&self) -> Option<&str> {
+        if let Some(ref subject_organization) = self.subject_organization {
+            return Some(subject_organization.as_str())
+        }
+
+        None
+    }
+
+    pub fn subject_organizational_unit(&self) -> Option<&str> {
+        if let Some(ref subject_organizational_unit) = self.subject_organizational_unit {
+            return Some(subject_organizational_unit.as_str())
+        }
+
+        None
+    }
+
+    /// Key usages requested through the extension request attribute.
+    pub fn key_usages(&self) -> &[CertificateKeyUsage] {
+        self.key_usages.as_slice()
+    }
+
+    /// Extended key usages requested through the extension request attribute.
+    pub fn extended_key_usages(&self) -> &[CertificateExtendedKeyUsage] {
+        self.extended_key_usages.as_slice()
+    }
+
+    /// Every extension requested through the extension request attribute.
+    pub fn extensions(&self) -> &[CertificateExtension] {
+        self.extensions.as_slice()
+    }
+
+    pub fn challenge_password(&self) -> Option<&str> {
+        if let Some(ref challenge_password) = self.challenge_password {
+            return Some(challenge_password.as_str())
+        }
+
+        None
+    }
+
+    /// `DER` encoded subject public key info of the requested certificate.
+    pub fn public_key_der_encoded(&self) -> &[u8] {
+        self.public_key.as_slice()
+    }
+
+    pub fn raw_der_encoded(&self) -> &[u8] {
+        self.raw_der_encoded.as_slice()
+    }
+
+    /// `PEM` encoded request.
+    pub fn pem_encoded(&self) -> String {
+        encoding::pem("CERTIFICATE REQUEST", self.raw_der_encoded())
+    }
+}
+
+/// Builder of `PKCS#10` certificate signing requests, signed with the key of the requested
+/// certificate.
+#[derive(Clone, Debug)]
+pub struct CertificateRequestBuilder {
+    subject: CertificateName,
+    alternate_names: Vec<CertificateAlternateName>,
+    key_usages: Vec<CertificateKeyUsage>,
+    extended_key_usages: Vec<CertificateExtendedKeyUsage>,
+    challenge_password: Option<String>,
+}
+
+impl CertificateRequestBuilder {
+    pub fn new(subject: CertificateName) -> CertificateRequestBuilder {
+        CertificateRequestBuilder {
+            subject,
+            alternate_names: Default::default(),
+            key_usages: Default::default(),
+            extended_key_usages: Default::default(),
+            challenge_password: None,
+        }
+    }
+
+    /// Request an alternate name. Directory names are not supported, and hostnames, emails
+    /// and `URI`s must be `ASCII`.
+    pub fn with_alternate_name(mut self, name: CertificateAlternateName) -> CertificateRequestBuilder {
+        self.alternate_names.push(name);
+        self
+    }
+
+    pub fn with_key_usage(mut self, usage: CertificateKeyUsage) -> CertificateRequestBuilder {
+        self.key_usages.push(usage);
+        self
+    }
+
+    pub fn with_extended_key_usage(mut self, usage: CertificateExtendedKeyUsage) -> CertificateRequestBuilder {
+        self.extended_key_usages.push(usage);
+        self
+    }
+
+    pub fn with_challenge_password(mut self, password: &str) -> CertificateRequestBuilder {
+        self.challenge_password = Some(password.to_owned());
+        self
+    }
+
+    /// Encode the request for the public key of the signing key and sign it.
+    pub fn sign(&self, key: &SigningKey) -> Result<CertificateRequest, BuilderError> {
+        let mut extensions = Vec::new();
+
+        if !self.alternate_names.is_empty() {
+            extensions.push(CertificateExtension::alternate_names(self.alternate_names.as_slice(), self.subject.is_empty())?);
+        }
+
+        if !self.key_usages.is_empty() {
+            extensions.push(CertificateExtension::key_usages(self.key_usages.as_slice()));
+        }
+
+        if !self.extended_key_usages.is_empty() {
+            extensions.push(CertificateExtension::extended_key_usages(self.extended_key_usages.as_slice())?);
+        }
+
+        let mut attributes = Vec::new();
+
+        if let Some(ref password) = self.challenge_password {
+            attributes.push(attribute(CHALLENGE_PASSWORD_OID, encoding::element(0x0C, password.as_bytes()).as_slice())?);
+        }
+
+        if !extensions.is_empty() {
+            let extensions: Vec<Vec<u8>> = extensions.iter()
+                .map(|extension| extension.der_encoded())
+                .collect::<Option<_>>()
+                .ok_or(BuilderError::InvalidObjectIdentifier)?;
+
+            attributes.push(attribute(EXTENSION_REQUEST_OID, encoding::element(0x30, extensions.concat().as_slice()).as_slice())?);
+        }
+
+        let info = encoding::element(0x30, [
+            encoding::integer(&[0x00]),
+            self.subject.der_encoded(),
+            key.public_key_der_encoded(),
+            encoding::element(0xA0, attributes.concat().as_slice()),
+        ].concat().as_slice());
+
+        let signature = key.sign(info.as_slice())?;
+
+        let request = encoding::element(0x30, [
+            info,
+            key.algorithm_der_encoded(),
+            encoding::element(0x03, [&[0x00], signature.as_slice()].concat().as_slice()),
+        ].concat().as_slice());
+
+        CertificateRequest::parse(request.as_slice())
+            .ok_or(BuilderError::Signature(SignatureError::BadEncoding))
+    }
+}
+
+/// `DER` encoded attribute with a single value.
+fn attribute(oid: &str, value: &[u8]) -> Result<Vec<u8>, BuilderError> {
+    let oid = encoding::oid_from_string(oid)
+        .ok_or(BuilderError::InvalidObjectIdentifier)?;
+
+    Ok(encoding::element(0x30, [
+        encoding::element(0x06, oid.as_slice()),
+        encoding::element(0x31, value),
+    ].concat().as_slice()))
+}
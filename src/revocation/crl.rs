@@ -0,0 +1,640 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+    },
+
+    error::{Error as StdError},
+    cmp::{Ordering},
+};
+
+use chrono::{
+
+    DateTime,
+    TimeZone,
+    Utc,
+};
+
+use x509_parser::{
+
+    revocation_list::{CertificateRevocationList},
+    extensions::{ParsedExtension},
+    prelude::{FromDer},
+    time::{ASN1Time},
+    pem::{Pem},
+};
+
+use crate::{
+
+    certificate::{
+
+        CertificateAlternateName,
+        Certificate,
+    },
+
+    signature::{
+
+        SignatureError,
+        self,
+    },
+
+    revocation::{Issuer},
+    encoding::{self},
+};
+
+/// Object identifier of the delta CRL indicator extension, `2.5.29.27`.
+pub(crate) const DELTA_INDICATOR_OID: &str = "2.5.29.27";
+
+/// Object identifier of the issuing distribution point extension, `2.5.29.28`.
+pub(crate) const DISTRIBUTION_POINT_OID: &str = "2.5.29.28";
+
+/// Reason a revocation list could not be verified or merged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevocationError {
+    /// The revocation list or the issuer certificate is not correctly encoded.
+    BadEncoding,
+    /// The revocation list is not issued by the given certificate.
+    UnknownIssuer,
+    /// The key usage of the issuer does not allow signing revocation lists.
+    InvalidKeyUsage,
+    /// The signature algorithm, or its combination with the issuer key, is not supported.
+    UnsupportedAlgorithm,
+    /// The signature does not match the issuer key.
+    BadSignature,
+    /// The revocation list is issued after the given time.
+    NotValidYet,
+    /// The next update of the revocation list is due, or it does not announce one.
+    Stale,
+    /// A delta revocation list does not apply to the base revocation list.
+    BaseMismatch,
+}
+
+impl FmtDisplay for RevocationError {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.write_str(match self {
+            RevocationError::BadEncoding => "revocation list or issuer is not correctly encoded",
+            RevocationError::UnknownIssuer => "revocation list is not issued by the certificate",
+            RevocationError::InvalidKeyUsage => "issuer is not allowed to sign revocation lists",
+            RevocationError::UnsupportedAlgorithm => "revocation list signature algorithm is not supported",
+            RevocationError::BadSignature => "revocation list signature does not match the issuer key",
+            RevocationError::NotValidYet => "revocation list is not valid yet",
+            RevocationError::Stale => "revocation list is out of date",
+            RevocationError::BaseMismatch => "delta revocation list does not apply to the base revocation list",
+        })
+    }
+}
+
+impl StdError for RevocationError {}
+
+impl From<SignatureError> for RevocationError {
+    fn from(error: SignatureError) -> RevocationError {
+        match error {
+            SignatureError::UnsupportedAlgorithm |
+            SignatureError::AlgorithmMismatch => RevocationError::UnsupportedAlgorithm,
+            SignatureError::BadSignature => RevocationError::BadSignature,
+            SignatureError::BadEncoding |
+            SignatureError::Unsigned => RevocationError::BadEncoding,
+        }
+    }
+}
+
+/// Reason code of a revoked certificate entry, as defined in RFC 5280.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevocationReason {
+    Unspecified,
+    KeyCompromise,
+    CaCompromise,
+    AffiliationChanged,
+    Superseded,
+    CessationOfOperation,
+    CertificateHold,
+    RemoveFromCrl,
+    PrivilegeWithdrawn,
+    AaCompromise,
+}
+
+impl RevocationReason {
+    pub(crate) fn from_code(code: u8) -> Option<RevocationReason> {
+        match code {
+            0 => Some(RevocationReason::Unspecified),
+            1 => Some(RevocationReason::KeyCompromise),
+            2 => Some(RevocationReason::CaCompromise),
+            3 => Some(RevocationReason::AffiliationChanged),
+            4 => Some(RevocationReason::Superseded),
+            5 => Some(RevocationReason::CessationOfOperation),
+            6 => Some(RevocationReason::CertificateHold),
+            8 => Some(RevocationReason::RemoveFromCrl),
+            9 => Some(RevocationReason::PrivilegeWithdrawn),
+            10 => Some(RevocationReason::AaCompromise),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> u8 {
+        match self {
+            RevocationReason::Unspecified => 0,
+            RevocationReason::KeyCompromise => 1,
+            RevocationReason::CaCompromise => 2,
+            RevocationReason::AffiliationChanged => 3,
+            RevocationReason::Superseded => 4,
+            RevocationReason::CessationOfOperation => 5,
+            RevocationReason::CertificateHold => 6,
+            RevocationReason::RemoveFromCrl => 8,
+            RevocationReason::PrivilegeWithdrawn => 9,
+            RevocationReason::AaCompromise => 10,
+        }
+    }
+}
+
+/// Entry of a revocation list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevokedCertificate {
+    serial: Vec<u8>,
+    revocation_time: DateTime<Utc>,
+    reason: Option<RevocationReason>,
+    invalidity_time: Option<DateTime<Utc>>,
+}
+
+impl RevokedCertificate {
+    /// Create an entry for a revoked serial number, such as to sign an `OCSP` response for it.
+    pub fn new(serial: &[u8], revocation_time: DateTime<Utc>, reason: Option<RevocationReason>) -> RevokedCertificate {
+        RevokedCertificate {
+            serial: encoding::unsigned(serial),
+            revocation_time,
+            reason,
+            invalidity_time: None,
+        }
+    }
+
+    /// Serial number of the revoked certificate, comparable to `Certificate::serial`.
+    pub fn serial(&self) -> &[u8] {
+        self.serial.as_slice()
+    }
+
+    pub fn revocation_time(&self) -> DateTime<Utc> {
+        self.revocation_time
+    }
+
+    pub fn reason(&self) -> Option<RevocationReason> {
+        self.reason
+    }
+
+    /// Time from which the key is known or suspected to have been compromised.
+    pub fn invalidity_time(&self) -> Option<DateTime<Utc>> {
+        self.invalidity_time
+    }
+}
+
+/// Scope of a revocation list, from its issuing distribution point extension.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IssuingDistributionPoint {
+    names: Vec<CertificateAlternateName>,
+    only_user_certificates: bool,
+    only_authority_certificates: bool,
+    only_reasons: Option<Vec<RevocationReason>>,
+    indirect: bool,
+    only_attribute_certificates: bool,
+}
+
+impl IssuingDistributionPoint {
+    pub(crate) fn parse(data: &[u8]) -> Option<IssuingDistributionPoint> {
+        let mut point = IssuingDistributionPoint::default();
+        let mut data = encoding::element_contents(data, 0x30)?;
+
+        while !data.is_empty() {
+            let (tag, contents, rest) = encoding::read_element(data)?;
+            match tag {
+                0xA0 => {
+                    // Only the full name form of the distribution point name is kept.
+                    if let Some(mut names) = encoding::element_contents(contents, 0xA0) {
+                        while !names.is_empty() {
+                            let (tag, name, rest) = encoding::read_element(names)?;
+                            let name = String::from_utf8(name.to_vec()).ok();
+
+                            match (tag, name) {
+                                (0x81, Some(name)) => point.names.push(CertificateAlternateName::Email(name)),
+                                (0x82, Some(name)) => point.names.push(CertificateAlternateName::Hostname(name)),
+                                (0x86, Some(name)) => point.names.push(CertificateAlternateName::Uri(name)),
+                                _ => {},
+                            }
+
+                            names = rest;
+                        }
+                    }
+                },
+                0x81 => point.only_user_certificates = contents.first() == Some(&0xFF),
+                0x82 => point.only_authority_certificates = contents.first() == Some(&0xFF),
+                0x83 => {
+                    let (_, bits) = contents.split_first()?;
+
+                    // Bit positions of `ReasonFlags` follow the reason codes, except for
+                    // the two codes after the unused value 7.
+                    point.only_reasons = Some((1..9).filter(|bit| {
+                        bits.get(bit / 8).map(|octet| octet & (0x80 >> (bit % 8)) != 0).unwrap_or(false)
+                    }).filter_map(|bit| match bit {
+                        7 => RevocationReason::from_code(9),
+                        8 => RevocationReason::from_code(10),
+                        bit => RevocationReason::from_code(bit as u8),
+                    }).collect());
+                },
+                0x84 => point.indirect = contents.first() == Some(&0xFF),
+                0x85 => point.only_attribute_certificates = contents.first() == Some(&0xFF),
+                _ => return None,
+            }
+
+            data = rest;
+        }
+
+        Some(point)
+    }
+
+    /// Full names of the distribution point the revocation list is published at.
+    pub fn names(&self) -> &[CertificateAlternateName] {
+        self.names.as_slice()
+    }
+
+    pub fn only_user_certificates(&self) -> bool {
+        self.only_user_certificates
+    }
+
+    pub fn only_authority_certificates(&self) -> bool {
+        self.only_authority_certificates
+    }
+
+    /// Reasons the revocation list is limited to, if it only covers some of them.
+    pub fn only_reasons(&self) -> Option<&[RevocationReason]> {
+        self.only_reasons.as_deref()
+    }
+
+    pub fn indirect(&self) -> bool {
+        self.indirect
+    }
+
+    pub fn only_attribute_certificates(&self) -> bool {
+        self.only_attribute_certificates
+    }
+}
+
+/// Certificate revocation list, as defined in RFC 5280.
+#[derive(Clone, Debug)]
+pub struct RevocationList {
+    issuer: Vec<u8>,
+    issuer_name: Option<String>,
+    this_update: DateTime<Utc>,
+    next_update: Option<DateTime<Utc>>,
+    number: Option<Vec<u8>>,
+    delta_indicator: Option<Vec<u8>>,
+    distribution_point: Option<IssuingDistributionPoint>,
+    authority_key_identifier: Option<Vec<u8>>,
+    entries: Vec<RevokedCertificate>,
+    raw_der_encoded: Vec<u8>,
+}
+
+impl RevocationList {
+    /// Parse a revocation list from `DER` encoded data.
+    pub fn parse(data: &[u8]) -> Option<RevocationList> {
+        let (remaining, list) = CertificateRevocationList::from_der(data).ok()?;
+
+        let issuer_name = list.issuer().iter_common_name()
+            .filter_map(|name| name.as_str().ok())
+            .next().map(|name| name.to_owned());
+
+        let mut delta_indicator = None;
+        let mut distribution_point = None;
+        let mut authority_key_identifier = None;
+
+        for extension in list.extensions() {
+            match extension.oid.to_id_string().as_str() {
+                DELTA_INDICATOR_OID => {
+                    delta_indicator = Some(encoding::unsigned(encoding::element_contents(extension.value, 0x02)?));
+                },
+                DISTRIBUTION_POINT_OID => {
+                    distribution_point = Some(IssuingDistributionPoint::parse(extension.value)?);
+                },
+                _ => {
+                    if let ParsedExtension::AuthorityKeyIdentifier(identifier) = extension.parsed_extension() {
+                        authority_key_identifier = identifier.key_identifier.as_ref()
+                            .map(|identifier| identifier.0.to_vec());
+                    }
+                },
+            }
+        }
+
+        let entries = list.iter_revoked_certificates().map(|entry| {
+            RevokedCertificate {
+                serial: entry.serial().to_bytes_be(),
+                revocation_time: time(&(entry.revocation_date)),
+                reason: entry.reason_code()
+                    .and_then(|(_, reason)| RevocationReason::from_code(reason.0)),
+                invalidity_time: entry.invalidity_date()
+                    .map(|(_, date)| time(&date)),
+            }
+        }).collect();
+
+        Some(RevocationList {
+            issuer: list.issuer().as_raw().to_vec(),
+            issuer_name,
+            this_update: time(&(list.last_update())),
+            next_update: list.next_update().map(|update| time(&update)),
+            number: list.crl_number().map(|number| number.to_bytes_be()),
+            delta_indicator,
+            distribution_point,
+            authority_key_identifier,
+            entries,
+            raw_der_encoded: data[..(data.len() - remaining.len())].to_vec(),
+        })
+    }
+
+    /// Parse the first revocation list in `PEM` encoded data.
+    pub fn from_pem(data: &[u8]) -> Option<RevocationList> {
+        let pem = Pem::iter_from_buffer(data).flatten()
+            .find(|pem| pem.label == "X509 CRL")?;
+
+        RevocationList::parse(pem.contents.as_slice())
+    }
+
+    /// Verify that the revocation list was signed by the key of the issuer, and that the
+    /// issuer is allowed to sign revocation lists.
+    pub fn verify_signed_by(&self, issuer: &Certificate) -> Result<(), RevocationError> {
+        let issuer = Issuer::from_certificate(issuer)
+            .ok_or(RevocationError::BadEncoding)?;
+
+        self.verify_issued_by(&issuer)
+    }
+
+    pub(crate) fn verify_issued_by(&self, issuer: &Issuer<'_>) -> Result<(), RevocationError> {
+        if issuer.subject_der_encoded() != self.issuer_der_encoded() {
+            return Err(RevocationError::UnknownIssuer)
+        }
+
+        if let (Some(authority), Some(subject)) = (self.authority_key_identifier(), issuer.key_identifier()) {
+            if authority != subject.as_slice() {
+                return Err(RevocationError::UnknownIssuer)
+            }
+        }
+
+        // A trust anchor known only by its subject and key carries no key usage to check.
+        if let Some(certificate) = issuer.certificate() {
+            let certificate = certificate.tbs_certificate()
+                .ok_or(RevocationError::BadEncoding)?;
+
+            match certificate.key_usage() {
+                Ok(Some(extension)) if !extension.value.crl_sign() => return Err(RevocationError::InvalidKeyUsage),
+                Ok(..) => {},
+                Err(..) => return Err(RevocationError::BadEncoding),
+            }
+        }
+
+        let (tbs, algorithm, signature) = signature::split_signed(self.raw_der_encoded())
+            .ok_or(RevocationError::BadEncoding)?;
+
+        if list_algorithm(tbs) != Some(algorithm) {
+            return Err(RevocationError::UnsupportedAlgorithm)
+        }
+
+        Ok(signature::verify(issuer.public_key_der_encoded(), algorithm, tbs, signature)?)
+    }
+
+    /// Verify the signature of the revocation list by the issuer, and that it is current
+    /// at the given time.
+    pub fn verify_at(&self, issuer: &Certificate, time: DateTime<Utc>) -> Result<(), RevocationError> {
+        let issuer = Issuer::from_certificate(issuer)
+            .ok_or(RevocationError::BadEncoding)?;
+
+        self.verify_issued_at(&issuer, time)
+    }
+
+    pub(crate) fn verify_issued_at(&self, issuer: &Issuer<'_>, time: DateTime<Utc>) -> Result<(), RevocationError> {
+        self.verify_issued_by(issuer)?;
+
+        if self.this_update > time {
+            return Err(RevocationError::NotValidYet)
+        }
+
+        if !self.is_fresh_at(time) {
+            return Err(RevocationError::Stale)
+        }
+
+        Ok(())
+    }
+
+    /// Whether the revocation list is current at the given time. A revocation list that
+    /// does not announce its next update is never considered current.
+    pub fn is_fresh_at(&self, time: DateTime<Utc>) -> bool {
+        if let Some(next_update) = self.next_update {
+            return self.this_update <= time && time < next_update
+        }
+
+        false
+    }
+
+    pub fn issuer_name(&self) -> Option<&str> {
+        if let Some(ref issuer_name) = self.issuer_name {
+            return Some(issuer_name.as_str())
+        }
+
+        None
+    }
+
+    pub fn this_update(&self) -> DateTime<Utc> {
+        self.this_update
+    }
+
+    pub fn next_update(&self) -> Option<DateTime<Utc>> {
+        self.next_update
+    }
+
+    /// CRL number, big-endian without leading zeros.
+    pub fn number(&self) -> Option<&[u8]> {
+        self.number.as_deref()
+    }
+
+    /// CRL number of the base revocation list, if this is a delta revocation list.
+    pub fn delta_indicator(&self) -> Option<&[u8]> {
+        self.delta_indicator.as_deref()
+    }
+
+    pub fn is_delta(&self) -> bool {
+        self.delta_indicator.is_some()
+    }
+
+    pub fn distribution_point(&self) -> Option<&IssuingDistributionPoint> {
+        self.distribution_point.as_ref()
+    }
+
+    pub fn entries(&self) -> &[RevokedCertificate] {
+        self.entries.as_slice()
+    }
+
+    /// Find the entry for a serial number.
+    pub fn find(&self, serial: &[u8]) -> Option<&RevokedCertificate> {
+        self.entries.iter().find(|entry| entry.serial() == serial)
+    }
+
+    pub fn raw_der_encoded(&self) -> &[u8] {
+        self.raw_der_encoded.as_slice()
+    }
+
+    pub(crate) fn issuer_der_encoded(&self) -> &[u8] {
+        self.issuer.as_slice()
+    }
+
+    pub(crate) fn authority_key_identifier(&self) -> Option<&[u8]> {
+        self.authority_key_identifier.as_deref()
+    }
+}
+
+/// Effective revocation set of a base revocation list with its delta revocation lists applied.
+#[derive(Clone, Debug)]
+pub struct RevocationSet {
+    issuer: Vec<u8>,
+    issuer_name: Option<String>,
+    this_update: DateTime<Utc>,
+    next_update: Option<DateTime<Utc>>,
+    number: Option<Vec<u8>>,
+    base_number: Option<Vec<u8>>,
+    distribution_point: Option<IssuingDistributionPoint>,
+    entries: Vec<RevokedCertificate>,
+}
+
+impl RevocationSet {
+    /// Apply delta revocation lists to a base revocation list, in order of their CRL number.
+    /// Entries marked `RemoveFromCrl` by a delta are dropped from the set. Signatures and
+    /// freshness are not checked here, verify each revocation list before merging.
+    pub fn merge(base: &RevocationList, deltas: &[RevocationList]) -> Result<RevocationSet, RevocationError> {
+        if base.is_delta() {
+            return Err(RevocationError::BaseMismatch)
+        }
+
+        let mut set = RevocationSet::from(base);
+
+        let mut deltas: Vec<&RevocationList> = deltas.iter().collect();
+        deltas.sort_by(|a, b| compare(a.number().unwrap_or_default(), b.number().unwrap_or_default()));
+
+        for delta in deltas {
+            let (Some(base_number), Some(indicator), Some(number)) = (base.number(), delta.delta_indicator(), delta.number()) else {
+                return Err(RevocationError::BaseMismatch)
+            };
+
+            // The delta must build on this base or an earlier one, and be newer than it.
+            if compare(base_number, indicator) == Ordering::Less || compare(number, base_number) != Ordering::Greater {
+                return Err(RevocationError::BaseMismatch)
+            }
+
+            if delta.issuer != base.issuer || delta.distribution_point != base.distribution_point {
+                return Err(RevocationError::BaseMismatch)
+            }
+
+            for entry in delta.entries() {
+                set.entries.retain(|existing| existing.serial != entry.serial);
+                if entry.reason != Some(RevocationReason::RemoveFromCrl) {
+                    set.entries.push(entry.clone());
+                }
+            }
+
+            set.this_update = delta.this_update;
+            set.next_update = delta.next_update;
+            set.number = delta.number.clone();
+        }
+
+        Ok(set)
+    }
+
+    pub fn issuer_name(&self) -> Option<&str> {
+        if let Some(ref issuer_name) = self.issuer_name {
+            return Some(issuer_name.as_str())
+        }
+
+        None
+    }
+
+    /// Issue time of the newest revocation list in the set.
+    pub fn this_update(&self) -> DateTime<Utc> {
+        self.this_update
+    }
+
+    /// Next update of the newest revocation list in the set.
+    pub fn next_update(&self) -> Option<DateTime<Utc>> {
+        self.next_update
+    }
+
+    /// CRL number of the newest revocation list in the set.
+    pub fn number(&self) -> Option<&[u8]> {
+        self.number.as_deref()
+    }
+
+    /// CRL number of the base revocation list.
+    pub fn base_number(&self) -> Option<&[u8]> {
+        self.base_number.as_deref()
+    }
+
+    pub fn distribution_point(&self) -> Option<&IssuingDistributionPoint> {
+        self.distribution_point.as_ref()
+    }
+
+    pub fn entries(&self) -> &[RevokedCertificate] {
+        self.entries.as_slice()
+    }
+
+    /// Find the entry for a serial number.
+    pub fn find(&self, serial: &[u8]) -> Option<&RevokedCertificate> {
+        self.entries.iter().find(|entry| entry.serial() == serial)
+    }
+
+    pub fn is_fresh_at(&self, time: DateTime<Utc>) -> bool {
+        if let Some(next_update) = self.next_update {
+            return self.this_update <= time && time < next_update
+        }
+
+        false
+    }
+
+    pub(crate) fn issuer_der_encoded(&self) -> &[u8] {
+        self.issuer.as_slice()
+    }
+}
+
+impl From<&RevocationList> for RevocationSet {
+    fn from(list: &RevocationList) -> RevocationSet {
+        RevocationSet {
+            issuer: list.issuer.clone(),
+            issuer_name: list.issuer_name.clone(),
+            this_update: list.this_update,
+            next_update: list.next_update,
+            number: list.number.clone(),
+            base_number: list.number.clone(),
+            distribution_point: list.distribution_point.clone(),
+            entries: list.entries.clone(),
+        }
+    }
+}
+
+/// Signature algorithm named inside a `DER` encoded to-be-signed revocation list.
+fn list_algorithm(tbs: &[u8]) -> Option<&[u8]> {
+    let mut contents = encoding::element_contents(tbs, 0x30)?;
+
+    let (tag, _, remaining) = encoding::read_element(contents)?;
+    if tag == 0x02 {
+        contents = remaining;
+    }
+
+    let (tag, _, after) = encoding::read_element(contents)?;
+    if tag != 0x30 {
+        return None
+    }
+
+    Some(&(contents[..(contents.len() - after.len())]))
+}
+
+/// Order of two unsigned big-endian numbers without leading zeros.
+fn compare(a: &[u8], b: &[u8]) -> Ordering {
+    a.len().cmp(&(b.len())).then_with(|| a.cmp(b))
+}
+
+fn time(time: &ASN1Time) -> DateTime<Utc> {
+    Utc.timestamp_opt(time.timestamp(), 0).single()
+        .unwrap_or_default()
+}
@@ -0,0 +1,181 @@
+pub mod crl;
+pub use crl::{
+
+    IssuingDistributionPoint,
+    RevocationReason,
+    RevokedCertificate,
+    RevocationError,
+    RevocationList,
+    RevocationSet,
+};
+
+pub mod policy;
+pub use policy::{
+
+    RevocationOptions,
+    RevocationPolicy,
+};
+
+pub mod ocsp;
+pub use ocsp::{
+
+    OcspCertificateId,
+    OcspResponseStatus,
+    OcspSingleResponse,
+    OcspHashAlgorithm,
+    OcspResponse,
+    OcspRequest,
+    OcspError,
+};
+
+pub mod responder;
+pub use responder::{
+
+    OcspResponder,
+};
+
+use x509_parser::{
+
+    x509::{SubjectPublicKeyInfo},
+    prelude::{FromDer},
+};
+
+use crate::{
+
+    certificate::{Certificate},
+    store::{TrustAnchor},
+    verification::{self},
+    encoding::{self},
+};
+
+/// Revocation status of a certificate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevocationStatus {
+    Good,
+    Revoked(RevokedCertificate),
+    Unknown,
+}
+
+impl RevocationStatus {
+    pub fn is_revoked(&self) -> bool {
+        matches!(self, RevocationStatus::Revoked(..))
+    }
+}
+
+/// Subject and public key of the issuer of a checked certificate, which is all revocation
+/// checking needs. A trust anchor known only by its subject and key has no certificate.
+#[derive(Clone, Debug)]
+pub(crate) struct Issuer<'a> {
+    subject: Vec<u8>,
+    public_key: Vec<u8>,
+    certificate: Option<&'a Certificate>,
+}
+
+impl<'a> Issuer<'a> {
+    pub(crate) fn from_certificate(certificate: &'a Certificate) -> Option<Issuer<'a>> {
+        Some(Issuer {
+            subject: certificate.subject_der_encoded()?,
+            public_key: certificate.public_key_der_encoded()?,
+            certificate: Some(certificate),
+        })
+    }
+
+    pub(crate) fn from_anchor(anchor: &'a TrustAnchor) -> Issuer<'a> {
+        Issuer {
+            subject: encoding::element(0x30, anchor.subject.as_slice()),
+            public_key: encoding::element(0x30, anchor.spki.as_slice()),
+            certificate: anchor.certificate.as_ref(),
+        }
+    }
+
+    pub(crate) fn subject_der_encoded(&self) -> &[u8] {
+        self.subject.as_slice()
+    }
+
+    pub(crate) fn public_key_der_encoded(&self) -> &[u8] {
+        self.public_key.as_slice()
+    }
+
+    pub(crate) fn certificate(&self) -> Option<&'a Certificate> {
+        self.certificate
+    }
+
+    /// Bits of the public key, as hashed into `OCSP` identifiers.
+    pub(crate) fn key_bits(&self) -> Option<Vec<u8>> {
+        let (_, public_key) = SubjectPublicKeyInfo::from_der(self.public_key.as_slice()).ok()?;
+        Some(public_key.subject_public_key.data.to_vec())
+    }
+
+    pub(crate) fn key_identifier(&self) -> Option<Vec<u8>> {
+        self.certificate.and_then(|certificate| certificate.subject_key_identifier())
+    }
+
+    /// Whether the certificate was issued and signed by the issuer.
+    pub(crate) fn signed(&self, certificate: &Certificate) -> bool {
+        let issued = match self.certificate {
+            Some(issuer) => verification::issued_by(certificate, issuer),
+            None => certificate.issuer_der_encoded().as_deref() == Some(self.subject_der_encoded()),
+        };
+
+        issued && certificate.verify_signed_by_key(self.public_key_der_encoded()).is_ok()
+    }
+}
+
+/// Whether a revocation list with the given issuer and distribution point covers the certificate.
+fn covers(certificate: &Certificate, issuer: &[u8], point: Option<&IssuingDistributionPoint>) -> bool {
+    if certificate.issuer_der_encoded().as_deref() != Some(issuer) {
+        return false
+    }
+
+    if let Some(point) = point {
+        if point.only_attribute_certificates() || point.indirect() || point.only_reasons().is_some() {
+            return false
+        }
+
+        if point.only_user_certificates() && certificate.authority() {
+            return false
+        }
+
+        if point.only_authority_certificates() && !certificate.authority() {
+            return false
+        }
+    }
+
+    true
+}
+
+fn status(entry: Option<&RevokedCertificate>) -> RevocationStatus {
+    match entry {
+        // Entries removed from the revocation list only appear in delta revocation lists.
+        Some(entry) if entry.reason() == Some(RevocationReason::RemoveFromCrl) => RevocationStatus::Good,
+        Some(entry) => RevocationStatus::Revoked(entry.clone()),
+        None => RevocationStatus::Good,
+    }
+}
+
+/// Look up a certificate in a revocation list. The status is unknown when the list is
+/// issued by another authority or its distribution point does not cover the certificate.
+pub(crate) fn check(certificate: &Certificate, list: &RevocationList) -> RevocationStatus {
+    if !covers(certificate, list.issuer_der_encoded(), list.distribution_point()) {
+        return RevocationStatus::Unknown
+    }
+
+    status(list.find(certificate.serial()))
+}
+
+/// Look up a certificate in a merged revocation set.
+pub(crate) fn check_set(certificate: &Certificate, set: &RevocationSet) -> RevocationStatus {
+    if !covers(certificate, set.issuer_der_encoded(), set.distribution_point()) {
+        return RevocationStatus::Unknown
+    }
+
+    status(set.find(certificate.serial()))
+}
+
+/// Look up a certificate issued by the given issuer in an `OCSP` response.
+pub(crate) fn check_response(certificate: &Certificate, issuer: &Certificate, response: &OcspResponse) -> RevocationStatus {
+    match response.find(certificate, issuer) {
+        Some(response) => response.status().clone(),
+        None => RevocationStatus::Unknown,
+    }
+}
@@ -0,0 +1,657 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+    },
+
+    error::{Error as StdError},
+};
+
+use chrono::{
+
+    DateTime,
+    Utc,
+};
+
+use ring::digest::{
+
+    SHA1_FOR_LEGACY_USE_ONLY as SHA1,
+    SHA256,
+    digest,
+};
+
+use crate::{
+
+    revocation::{
+
+        RevocationStatus,
+        RevocationReason,
+        RevokedCertificate,
+        Issuer,
+    },
+
+    signature::{
+
+        SignatureError,
+        self,
+    },
+
+    certificate::{Certificate},
+    encoding::{self},
+};
+
+/// Object identifier contents of the basic `OCSP` response type, `1.3.6.1.5.5.7.48.1.1`.
+pub(crate) const BASIC_RESPONSE_OID: &[u8] = &[0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x01];
+
+/// Object identifier contents of the `OCSP` nonce extension, `1.3.6.1.5.5.7.48.1.2`.
+pub(crate) const NONCE_OID: &[u8] = &[0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x02];
+
+/// Reason an `OCSP` response could not be verified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OcspError {
+    /// The response or the issuer certificate is not correctly encoded.
+    BadEncoding,
+    /// The responder did not answer with a successful response.
+    Unsuccessful(OcspResponseStatus),
+    /// The response is signed neither by the issuer nor by a responder certificate it issued.
+    UnknownResponder,
+    /// The delegated responder certificate is not valid for signing `OCSP` responses.
+    InvalidResponder,
+    /// The signature algorithm, or its combination with the responder key, is not supported.
+    UnsupportedAlgorithm,
+    /// The signature does not match the responder key.
+    BadSignature,
+}
+
+impl FmtDisplay for OcspError {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.write_str(match self {
+            OcspError::BadEncoding => "response or issuer is not correctly encoded",
+            OcspError::Unsuccessful(..) => "responder did not answer successfully",
+            OcspError::UnknownResponder => "response is signed by an unknown responder",
+            OcspError::InvalidResponder => "responder is not allowed to sign responses",
+            OcspError::UnsupportedAlgorithm => "response signature algorithm is not supported",
+            OcspError::BadSignature => "response signature does not match the responder key",
+        })
+    }
+}
+
+impl StdError for OcspError {}
+
+impl From<SignatureError> for OcspError {
+    fn from(error: SignatureError) -> OcspError {
+        match error {
+            SignatureError::UnsupportedAlgorithm |
+            SignatureError::AlgorithmMismatch => OcspError::UnsupportedAlgorithm,
+            SignatureError::BadSignature => OcspError::BadSignature,
+            SignatureError::BadEncoding |
+            SignatureError::Unsigned => OcspError::BadEncoding,
+        }
+    }
+}
+
+/// Hash algorithm of a certificate identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OcspHashAlgorithm {
+    Sha1,
+    Sha256,
+}
+
+impl OcspHashAlgorithm {
+    fn from_oid(oid: &[u8]) -> Option<OcspHashAlgorithm> {
+        match oid {
+            [0x2B, 0x0E, 0x03, 0x02, 0x1A] => Some(OcspHashAlgorithm::Sha1),
+            [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01] => Some(OcspHashAlgorithm::Sha256),
+            _ => None,
+        }
+    }
+
+    fn oid(&self) -> &'static [u8] {
+        match self {
+            OcspHashAlgorithm::Sha1 => &[0x2B, 0x0E, 0x03, 0x02, 0x1A],
+            OcspHashAlgorithm::Sha256 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01],
+        }
+    }
+
+    pub(crate) fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            OcspHashAlgorithm::Sha1 => digest(&SHA1, data).as_ref().to_vec(),
+            OcspHashAlgorithm::Sha256 => digest(&SHA256, data).as_ref().to_vec(),
+        }
+    }
+}
+
+/// Identifier of a certificate in `OCSP` requests and responses, by hashes of the name and
+/// key of its issuer and its serial number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OcspCertificateId {
+    hash_algorithm: OcspHashAlgorithm,
+    issuer_name_hash: Vec<u8>,
+    issuer_key_hash: Vec<u8>,
+    serial: Vec<u8>,
+}
+
+impl OcspCertificateId {
+    /// Identify a certificate issued by the given issuer.
+    pub fn new(certificate: &Certificate, issuer: &Certificate, hash_algorithm: OcspHashAlgorithm) -> Option<OcspCertificateId> {
+        OcspCertificateId::from_serial(certificate.serial(), &(Issuer::from_certificate(issuer)?), hash_algorithm)
+    }
+
+    pub(crate) fn from_serial(serial: &[u8], issuer: &Issuer<'_>, hash_algorithm: OcspHashAlgorithm) -> Option<OcspCertificateId> {
+        Some(OcspCertificateId {
+            hash_algorithm,
+            issuer_name_hash: hash_algorithm.digest(issuer.subject_der_encoded()),
+            issuer_key_hash: hash_algorithm.digest(issuer.key_bits()?.as_slice()),
+            serial: encoding::unsigned(serial),
+        })
+    }
+
+    pub(crate) fn parse(data: &[u8]) -> Option<OcspCertificateId> {
+        let contents = encoding::element_contents(data, 0x30)?;
+
+        let (_, algorithm, contents) = encoding::read_element(contents)?;
+        let hash_algorithm = OcspHashAlgorithm::from_oid(encoding::element_contents(algorithm, 0x06)?)?;
+
+        let (_, issuer_name_hash, contents) = encoding::read_element(contents)?;
+        let (_, issuer_key_hash, contents) = encoding::read_element(contents)?;
+
+        Some(OcspCertificateId {
+            hash_algorithm,
+            issuer_name_hash: issuer_name_hash.to_vec(),
+            issuer_key_hash: issuer_key_hash.to_vec(),
+            serial: encoding::unsigned(encoding::element_contents(contents, 0x02)?),
+        })
+    }
+
+    pub fn hash_algorithm(&self) -> OcspHashAlgorithm {
+        self.hash_algorithm
+    }
+
+    pub fn issuer_name_hash(&self) -> &[u8] {
+        self.issuer_name_hash.as_slice()
+    }
+
+    pub fn issuer_key_hash(&self) -> &[u8] {
+        self.issuer_key_hash.as_slice()
+    }
+
+    /// Serial number of the certificate, comparable to `Certificate::serial`.
+    pub fn serial(&self) -> &[u8] {
+        self.serial.as_slice()
+    }
+
+    /// Whether the identifier refers to the certificate issued by the given issuer.
+    pub fn matches(&self, certificate: &Certificate, issuer: &Certificate) -> bool {
+        OcspCertificateId::new(certificate, issuer, self.hash_algorithm).as_ref() == Some(self)
+    }
+
+    pub(crate) fn identifies(&self, certificate: &Certificate, issuer: &Issuer<'_>) -> bool {
+        OcspCertificateId::from_serial(certificate.serial(), issuer, self.hash_algorithm).as_ref() == Some(self)
+    }
+
+    pub(crate) fn der_encoded(&self) -> Vec<u8> {
+        let algorithm = [
+            encoding::element(0x06, self.hash_algorithm.oid()),
+            encoding::element(0x05, &[]),
+        ].concat();
+
+        encoding::element(0x30, [
+            encoding::element(0x30, algorithm.as_slice()),
+            encoding::element(0x04, self.issuer_name_hash.as_slice()),
+            encoding::element(0x04, self.issuer_key_hash.as_slice()),
+            encoding::integer(self.serial.as_slice()),
+        ].concat().as_slice())
+    }
+}
+
+/// Unsigned `OCSP` request for the status of a certificate, as defined in RFC 6960.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OcspRequest {
+    id: OcspCertificateId,
+    nonce: Option<Vec<u8>>,
+}
+
+impl OcspRequest {
+    /// Create a request for a certificate issued by the given issuer.
+    pub fn new(certificate: &Certificate, issuer: &Certificate, hash_algorithm: OcspHashAlgorithm) -> Option<OcspRequest> {
+        Some(OcspRequest {
+            id: OcspCertificateId::new(certificate, issuer, hash_algorithm)?,
+            nonce: None,
+        })
+    }
+
+    /// Parse a request from `DER` encoded data. Signed requests are accepted without checking
+    /// the signature, and only the first certificate of a request for several is kept.
+    pub fn parse(data: &[u8]) -> Option<OcspRequest> {
+        let mut tbs = encoding::element_contents(encoding::element_contents(data, 0x30)?, 0x30)?;
+
+        let (tag, _, rest) = encoding::read_element(tbs)?;
+        if tag == 0xA0 {
+            tbs = rest;
+        }
+
+        let (tag, _, rest) = encoding::read_element(tbs)?;
+        if tag == 0xA1 {
+            tbs = rest;
+        }
+
+        let (_, requests, rest) = encoding::read_element(tbs)?;
+        let (_, request, _) = encoding::read_element(requests)?;
+        let (_, _, after) = encoding::read_element(request)?;
+
+        let id = OcspCertificateId::parse(&(request[..(request.len() - after.len())]))?;
+        let nonce = match encoding::element_contents(rest, 0xA2) {
+            Some(extensions) => nonce(extensions)?,
+            None => None,
+        };
+
+        Some(OcspRequest { id, nonce })
+    }
+
+    /// Attach a nonce the responder is asked to echo, binding the response to this request.
+    pub fn with_nonce(mut self, nonce: &[u8]) -> OcspRequest {
+        self.nonce = Some(nonce.to_vec());
+        self
+    }
+
+    pub fn id(&self) -> &OcspCertificateId {
+        &(self.id)
+    }
+
+    pub fn nonce(&self) -> Option<&[u8]> {
+        self.nonce.as_deref()
+    }
+
+    pub fn der_encoded(&self) -> Vec<u8> {
+        let request = encoding::element(0x30, self.id.der_encoded().as_slice());
+        let mut tbs = encoding::element(0x30, request.as_slice());
+
+        if let Some(ref nonce) = self.nonce {
+            let extension = encoding::element(0x30, [
+                encoding::element(0x06, NONCE_OID),
+                encoding::element(0x04, encoding::element(0x04, nonce.as_slice()).as_slice()),
+            ].concat().as_slice());
+
+            let extensions = encoding::element(0x30, extension.as_slice());
+            tbs.extend(encoding::element(0xA2, extensions.as_slice()));
+        }
+
+        encoding::element(0x30, encoding::element(0x30, tbs.as_slice()).as_slice())
+    }
+}
+
+/// Status of an `OCSP` response as a whole.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OcspResponseStatus {
+    Successful,
+    MalformedRequest,
+    InternalError,
+    TryLater,
+    SignatureRequired,
+    Unauthorized,
+}
+
+impl OcspResponseStatus {
+    pub(crate) fn from_code(code: u8) -> Option<OcspResponseStatus> {
+        match code {
+            0 => Some(OcspResponseStatus::Successful),
+            1 => Some(OcspResponseStatus::MalformedRequest),
+            2 => Some(OcspResponseStatus::InternalError),
+            3 => Some(OcspResponseStatus::TryLater),
+            5 => Some(OcspResponseStatus::SignatureRequired),
+            6 => Some(OcspResponseStatus::Unauthorized),
+            _ => None,
+        }
+    }
+}
+
+/// Status of a single certificate in an `OCSP` response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OcspSingleResponse {
+    id: OcspCertificateId,
+    status: RevocationStatus,
+    this_update: DateTime<Utc>,
+    next_update: Option<DateTime<Utc>>,
+}
+
+impl OcspSingleResponse {
+    fn parse(data: &[u8]) -> Option<OcspSingleResponse> {
+        let contents = encoding::element_contents(data, 0x30)?;
+
+        let (_, _, rest) = encoding::read_element(contents)?;
+        let id = OcspCertificateId::parse(&(contents[..(contents.len() - rest.len())]))?;
+
+        let (tag, status, rest) = encoding::read_element(rest)?;
+        let status = match tag {
+            0x80 => RevocationStatus::Good,
+            0xA1 => {
+                let (_, time, status) = encoding::read_element(status)?;
+                let reason = encoding::element_contents(status, 0xA0)
+                    .and_then(|reason| encoding::element_contents(reason, 0x0A))
+                    .and_then(|code| RevocationReason::from_code(*code.last()?));
+
+                RevocationStatus::Revoked(RevokedCertificate::new(id.serial(), encoding::generalized_time(time)?, reason))
+            },
+            0x82 => RevocationStatus::Unknown,
+            _ => return None,
+        };
+
+        let (_, this_update, rest) = encoding::read_element(rest)?;
+        let next_update = match encoding::element_contents(rest, 0xA0) {
+            Some(next_update) => Some(encoding::generalized_time(encoding::element_contents(next_update, 0x18)?)?),
+            None => None,
+        };
+
+        Some(OcspSingleResponse {
+            id,
+            status,
+            this_update: encoding::generalized_time(this_update)?,
+            next_update,
+        })
+    }
+
+    pub fn id(&self) -> &OcspCertificateId {
+        &(self.id)
+    }
+
+    pub fn status(&self) -> &RevocationStatus {
+        &(self.status)
+    }
+
+    pub fn this_update(&self) -> DateTime<Utc> {
+        self.this_update
+    }
+
+    pub fn next_update(&self) -> Option<DateTime<Utc>> {
+        self.next_update
+    }
+
+    /// Whether the status is current at the given time. A status that does not announce
+    /// its next update is never considered current.
+    pub fn is_fresh_at(&self, time: DateTime<Utc>) -> bool {
+        if let Some(next_update) = self.next_update {
+            return self.this_update <= time && time < next_update
+        }
+
+        false
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ResponderId {
+    Name(Vec<u8>),
+    KeyHash(Vec<u8>),
+}
+
+impl ResponderId {
+    fn matches(&self, issuer: &Issuer<'_>) -> bool {
+        match self {
+            ResponderId::Name(name) => issuer.subject_der_encoded() == name.as_slice(),
+            ResponderId::KeyHash(hash) => issuer.key_bits()
+                .map(|key| OcspHashAlgorithm::Sha1.digest(key.as_slice()) == *hash)
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// `OCSP` response, as defined in RFC 6960. Only basic responses are understood.
+#[derive(Clone, Debug)]
+pub struct OcspResponse {
+    status: OcspResponseStatus,
+    responder: Option<ResponderId>,
+    produced_at: Option<DateTime<Utc>>,
+    responses: Vec<OcspSingleResponse>,
+    nonce: Option<Vec<u8>>,
+    certificates: Vec<Certificate>,
+    raw_der_encoded: Vec<u8>,
+}
+
+impl OcspResponse {
+    /// Parse a response from `DER` encoded data.
+    pub fn parse(data: &[u8]) -> Option<OcspResponse> {
+        let (tag, contents, remaining) = encoding::read_element(data)?;
+        if tag != 0x30 {
+            return None
+        }
+
+        let (tag, status, rest) = encoding::read_element(contents)?;
+        if tag != 0x0A {
+            return None
+        }
+
+        let mut response = OcspResponse {
+            status: OcspResponseStatus::from_code(*status.last()?)?,
+            responder: None,
+            produced_at: None,
+            responses: Default::default(),
+            nonce: None,
+            certificates: Default::default(),
+            raw_der_encoded: data[..(data.len() - remaining.len())].to_vec(),
+        };
+
+        if response.status != OcspResponseStatus::Successful {
+            return Some(response)
+        }
+
+        let bytes = encoding::element_contents(encoding::element_contents(rest, 0xA0)?, 0x30)?;
+        let (_, oid, rest) = encoding::read_element(bytes)?;
+        if oid != BASIC_RESPONSE_OID {
+            return None
+        }
+
+        let basic = encoding::element_contents(encoding::element_contents(rest, 0x04)?, 0x30)?;
+        let (_, mut tbs, rest) = encoding::read_element(basic)?;
+
+        let (_, _, rest) = encoding::read_element(rest)?;
+        let (_, _, rest) = encoding::read_element(rest)?;
+
+        if let Some(certificates) = encoding::element_contents(rest, 0xA0) {
+            let mut certificates = encoding::element_contents(certificates, 0x30)?;
+            while !certificates.is_empty() {
+                let (_, _, rest) = encoding::read_element(certificates)?;
+                response.certificates.push(Certificate::parse(&(certificates[..(certificates.len() - rest.len())]))?);
+                certificates = rest;
+            }
+        }
+
+        let (tag, _, rest) = encoding::read_element(tbs)?;
+        if tag == 0xA0 {
+            tbs = rest;
+        }
+
+        let (tag, responder, rest) = encoding::read_element(tbs)?;
+        response.responder = Some(match tag {
+            0xA1 => ResponderId::Name(responder.to_vec()),
+            0xA2 => ResponderId::KeyHash(encoding::element_contents(responder, 0x04)?.to_vec()),
+            _ => return None,
+        });
+
+        let (_, produced_at, rest) = encoding::read_element(rest)?;
+        response.produced_at = Some(encoding::generalized_time(produced_at)?);
+
+        let (_, mut responses, rest) = encoding::read_element(rest)?;
+        while !responses.is_empty() {
+            let (_, _, remaining) = encoding::read_element(responses)?;
+            response.responses.push(OcspSingleResponse::parse(&(responses[..(responses.len() - remaining.len())]))?);
+            responses = remaining;
+        }
+
+        if let Some(extensions) = encoding::element_contents(rest, 0xA1) {
+            response.nonce = nonce(extensions)?;
+        }
+
+        Some(response)
+    }
+
+    /// Create an unsuccessful response, which carries no status and is not signed.
+    pub fn unsuccessful(status: OcspResponseStatus) -> Option<OcspResponse> {
+        let code = match status {
+            OcspResponseStatus::Successful => return None,
+            OcspResponseStatus::MalformedRequest => 1,
+            OcspResponseStatus::InternalError => 2,
+            OcspResponseStatus::TryLater => 3,
+            OcspResponseStatus::SignatureRequired => 5,
+            OcspResponseStatus::Unauthorized => 6,
+        };
+
+        OcspResponse::parse(encoding::element(0x30, encoding::element(0x0A, &[code]).as_slice()).as_slice())
+    }
+
+    pub fn status(&self) -> OcspResponseStatus {
+        self.status
+    }
+
+    pub fn produced_at(&self) -> Option<DateTime<Utc>> {
+        self.produced_at
+    }
+
+    pub fn responses(&self) -> &[OcspSingleResponse] {
+        self.responses.as_slice()
+    }
+
+    /// Nonce echoed by the responder, to compare with the one in the request.
+    pub fn nonce(&self) -> Option<&[u8]> {
+        self.nonce.as_deref()
+    }
+
+    /// Certificates included by the responder, such as a delegated responder certificate.
+    pub fn certificates(&self) -> &[Certificate] {
+        self.certificates.as_slice()
+    }
+
+    /// Find the status of a certificate issued by the given issuer.
+    pub fn find(&self, certificate: &Certificate, issuer: &Certificate) -> Option<&OcspSingleResponse> {
+        self.responses.iter().find(|response| response.id.matches(certificate, issuer))
+    }
+
+    pub(crate) fn find_issued(&self, certificate: &Certificate, issuer: &Issuer<'_>) -> Option<&OcspSingleResponse> {
+        self.responses.iter().find(|response| response.id.identifies(certificate, issuer))
+    }
+
+    /// Verify that the response was signed by the issuer, or by a delegated responder whose
+    /// certificate is included in the response, issued by the issuer and allowed to sign
+    /// `OCSP` responses.
+    pub fn verify_signed_by(&self, issuer: &Certificate) -> Result<(), OcspError> {
+        let issuer = Issuer::from_certificate(issuer)
+            .ok_or(OcspError::BadEncoding)?;
+
+        self.signer(&issuer).map(|_| ())
+    }
+
+    /// Verify the signature of the response like `verify_signed_by`, and that a delegated
+    /// responder certificate is valid at the given time.
+    pub fn verify_at(&self, issuer: &Certificate, time: DateTime<Utc>) -> Result<(), OcspError> {
+        let issuer = Issuer::from_certificate(issuer)
+            .ok_or(OcspError::BadEncoding)?;
+
+        self.verify_issued_at(&issuer, time)
+    }
+
+    pub(crate) fn verify_issued_at(&self, issuer: &Issuer<'_>, time: DateTime<Utc>) -> Result<(), OcspError> {
+        if let Some(delegate) = self.signer(issuer)? {
+            if !delegate.validity().is_within_valid_time_at(time) {
+                return Err(OcspError::InvalidResponder)
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn raw_der_encoded(&self) -> &[u8] {
+        self.raw_der_encoded.as_slice()
+    }
+
+    /// Verify the signature, giving the delegated responder certificate that made it, if any.
+    fn signer(&self, issuer: &Issuer<'_>) -> Result<Option<&Certificate>, OcspError> {
+        if self.status != OcspResponseStatus::Successful {
+            return Err(OcspError::Unsuccessful(self.status))
+        }
+
+        let responder = self.responder.as_ref()
+            .ok_or(OcspError::BadEncoding)?;
+
+        let delegate = if responder.matches(issuer) { None } else {
+
+            let delegate = self.certificates.iter()
+                .find(|certificate| Issuer::from_certificate(certificate)
+                    .map(|certificate| responder.matches(&certificate))
+                    .unwrap_or(false))
+                .ok_or(OcspError::UnknownResponder)?;
+
+            if !issuer.signed(delegate) {
+                return Err(OcspError::UnknownResponder)
+            }
+
+            let signing = delegate.tbs_certificate()
+                .and_then(|certificate| certificate.extended_key_usage().ok().flatten()
+                    .map(|extension| extension.value.ocsp_signing))
+                .unwrap_or(false);
+
+            if !signing {
+                return Err(OcspError::InvalidResponder)
+            }
+
+            Some(delegate)
+        };
+
+        let (data, algorithm, signature) = basic_response(self.raw_der_encoded())
+            .and_then(signature::split_signed)
+            .ok_or(OcspError::BadEncoding)?;
+
+        let public_key = match delegate {
+            Some(delegate) => delegate.public_key_der_encoded()
+                .ok_or(OcspError::BadEncoding)?,
+            None => issuer.public_key_der_encoded().to_vec(),
+        };
+
+        signature::verify(public_key.as_slice(), algorithm, data, signature)?;
+
+        Ok(delegate)
+    }
+}
+
+/// Nonce in a `DER` encoded `Extensions` sequence, if present.
+fn nonce(extensions: &[u8]) -> Option<Option<Vec<u8>>> {
+    let mut extensions = encoding::element_contents(extensions, 0x30)?;
+
+    while !extensions.is_empty() {
+        let (_, extension, rest) = encoding::read_element(extensions)?;
+        let (_, oid, extension) = encoding::read_element(extension)?;
+
+        if oid == NONCE_OID {
+            let (tag, _, rest) = encoding::read_element(extension)?;
+            let value = if tag == 0x01 { encoding::element_contents(rest, 0x04)? }
+            else { encoding::element_contents(extension, 0x04)? };
+
+            // The nonce is an `OCTET STRING` inside the extension value, though some
+            // implementations put it there directly.
+            return Some(Some(encoding::element_contents(value, 0x04)
+                .unwrap_or(value).to_vec()))
+        }
+
+        extensions = rest;
+    }
+
+    Some(None)
+}
+
+/// The `DER` encoded basic response inside a successful `OCSP` response.
+fn basic_response(data: &[u8]) -> Option<&[u8]> {
+    let contents = encoding::element_contents(data, 0x30)?;
+    let (_, _, rest) = encoding::read_element(contents)?;
+
+    let bytes = encoding::element_contents(encoding::element_contents(rest, 0xA0)?, 0x30)?;
+    let (_, _, rest) = encoding::read_element(bytes)?;
+
+    encoding::element_contents(rest, 0x04)
+}
+
+/// Contents of the subject public key `BIT STRING` of a certificate, without the unused bits octet.
+pub(crate) fn key_bits(certificate: &Certificate) -> Option<Vec<u8>> {
+    certificate.tbs_certificate().map(|certificate| {
+        certificate.public_key().subject_public_key.data.to_vec()
+    })
+}
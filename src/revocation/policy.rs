@@ -0,0 +1,176 @@
+use chrono::{
+
+    DateTime,
+    Utc,
+};
+
+use crate::{
+
+    verification::{
+
+        VerificationReason,
+        VerificationError,
+    },
+
+    revocation::{
+
+        RevocationStatus,
+        RevocationList,
+        RevocationSet,
+        OcspResponse,
+        Issuer,
+    },
+
+    certificate::{Certificate},
+};
+
+/// How revocation is enforced during chain verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevocationPolicy {
+    /// Every certificate in the path must be known not to be revoked.
+    HardFail,
+    /// Revoked certificates fail, certificates without usable revocation data pass.
+    SoftFail,
+    /// Only the verified certificate itself is checked, and must be known not to be revoked.
+    LeafOnly,
+}
+
+/// Revocation data and policy to verify a certificate chain with.
+#[derive(Clone, Debug)]
+pub struct RevocationOptions {
+    policy: RevocationPolicy,
+    revocation_lists: Vec<RevocationList>,
+    ocsp_responses: Vec<OcspResponse>,
+}
+
+impl RevocationOptions {
+    pub fn new(policy: RevocationPolicy) -> RevocationOptions {
+        RevocationOptions {
+            policy,
+            revocation_lists: Default::default(),
+            ocsp_responses: Default::default(),
+        }
+    }
+
+    /// Attach a revocation list, base or delta, for any issuer in the chain.
+    pub fn with_revocation_list(mut self, list: RevocationList) -> RevocationOptions {
+        self.revocation_lists.push(list);
+        self
+    }
+
+    /// Attach an `OCSP` response for any certificate in the chain. A response stapled by a
+    /// server is used as well.
+    pub fn with_ocsp_response(mut self, response: OcspResponse) -> RevocationOptions {
+        self.ocsp_responses.push(response);
+        self
+    }
+
+    pub fn policy(&self) -> RevocationPolicy {
+        self.policy
+    }
+
+    pub fn revocation_lists(&self) -> &[RevocationList] {
+        self.revocation_lists.as_slice()
+    }
+
+    pub fn ocsp_responses(&self) -> &[OcspResponse] {
+        self.ocsp_responses.as_slice()
+    }
+}
+
+/// Status from two sources, where a revocation from either wins over a good status.
+fn combine(status: RevocationStatus, other: RevocationStatus) -> RevocationStatus {
+    match (status, other) {
+        (status @ RevocationStatus::Revoked(..), _) |
+        (_, status @ RevocationStatus::Revoked(..)) => status,
+        (RevocationStatus::Good, _) |
+        (_, RevocationStatus::Good) => RevocationStatus::Good,
+        _ => RevocationStatus::Unknown,
+    }
+}
+
+/// Status of a certificate from `OCSP` responses. Only responses signed for the issuer with
+/// a current status for the certificate are used.
+fn response_status<'a>(certificate: &Certificate, issuer: &Issuer<'_>, responses: impl Iterator<Item = &'a OcspResponse>, time: DateTime<Utc>) -> RevocationStatus {
+    responses.filter(|response| response.verify_issued_at(issuer, time).is_ok())
+        .filter_map(|response| response.find_issued(certificate, issuer))
+        .filter(|response| response.is_fresh_at(time))
+        .map(|response| response.status().clone())
+        .fold(RevocationStatus::Unknown, combine)
+}
+
+/// Status of a certificate from the revocation lists of its issuer. Only revocation lists
+/// signed by the issuer and current at the given time are used, with applicable delta
+/// revocation lists merged into each base.
+fn list_status(certificate: &Certificate, issuer: &Issuer<'_>, lists: &[RevocationList], time: DateTime<Utc>) -> RevocationStatus {
+    let lists: Vec<&RevocationList> = lists.iter()
+        .filter(|list| list.verify_issued_at(issuer, time).is_ok())
+        .collect();
+
+    let deltas: Vec<RevocationList> = lists.iter()
+        .filter(|list| list.is_delta())
+        .map(|list| (*list).clone())
+        .collect();
+
+    let mut status = RevocationStatus::Unknown;
+    for base in lists.iter().filter(|list| !list.is_delta()) {
+        let applicable: Vec<RevocationList> = deltas.iter()
+            .filter(|delta| RevocationSet::merge(base, std::slice::from_ref(*delta)).is_ok())
+            .cloned()
+            .collect();
+
+        let set = RevocationSet::merge(base, applicable.as_slice())
+            .unwrap_or_else(|_| RevocationSet::from(*base));
+
+        match certificate.check_revocation_set(&set) {
+            RevocationStatus::Revoked(entry) => return RevocationStatus::Revoked(entry),
+            RevocationStatus::Good => status = RevocationStatus::Good,
+            RevocationStatus::Unknown => {},
+        }
+    }
+
+    status
+}
+
+/// Determine the revocation status of every certificate in a verified path and enforce the
+/// policy on it, consulting both `OCSP` responses and revocation lists. A revocation from
+/// either source wins. Certificates outside the scope of the policy are reported as unknown.
+/// The last certificate is checked against the trust anchor, by its subject and public key.
+pub(crate) fn evaluate(path: &[Certificate], anchor: &Issuer<'_>, options: &RevocationOptions, stapled: Option<&OcspResponse>, time: DateTime<Utc>) -> Result<Vec<RevocationStatus>, VerificationError> {
+    let mut statuses = Vec::new();
+
+    for (depth, certificate) in path.iter().enumerate() {
+        if options.policy == RevocationPolicy::LeafOnly && depth > 0 {
+            statuses.push(RevocationStatus::Unknown);
+            continue
+        }
+
+        let issuer = match path.get(depth + 1) {
+            Some(issuer) => Issuer::from_certificate(issuer),
+            None => Some(anchor.clone()),
+        };
+
+        let status = match issuer {
+            Some(ref issuer) => combine(
+                response_status(certificate, issuer, options.ocsp_responses().iter().chain(stapled), time),
+                list_status(certificate, issuer, options.revocation_lists(), time),
+            ),
+            None => RevocationStatus::Unknown,
+        };
+
+        match (&status, options.policy) {
+            (RevocationStatus::Revoked(..), _) => {
+                return Err(VerificationError::new(VerificationReason::Revoked, Some(depth)))
+            },
+            (RevocationStatus::Unknown, RevocationPolicy::HardFail) |
+            (RevocationStatus::Unknown, RevocationPolicy::LeafOnly) => {
+                return Err(VerificationError::new(VerificationReason::RevocationUnknown, Some(depth)))
+            },
+            _ => {},
+        }
+
+        statuses.push(status);
+    }
+
+    Ok(statuses)
+}
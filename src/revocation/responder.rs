@@ -0,0 +1,234 @@
+use std::{
+
+    io::{Result as IoResult},
+    net::{ToSocketAddrs},
+    sync::{Arc, Mutex},
+};
+
+use base64::{
+
+    engine::general_purpose::{STANDARD as BASE64},
+    Engine,
+};
+
+use chrono::{
+
+    Duration,
+    DateTime,
+    Utc,
+};
+
+use crate::{
+
+    revocation::{
+
+        OcspCertificateId,
+        OcspResponseStatus,
+        OcspHashAlgorithm,
+        RevocationStatus,
+        OcspResponse,
+        OcspRequest,
+        OcspError,
+        Issuer,
+
+        ocsp::{self},
+    },
+
+    http::{
+
+        HttpResponse,
+        HttpRequest,
+        HttpServer,
+    },
+
+    certificate::{Certificate},
+    signature::{SigningKey},
+    encoding::{self},
+};
+
+#[derive(Default)]
+struct ResponderState {
+    statuses: Vec<(Vec<u8>, RevocationStatus)>,
+}
+
+/// `OCSP` responder for tests, signing responses for certificates of one issuer with a
+/// chosen status. Responses are signed by the issuer, or by a delegated responder
+/// certificate when one is given.
+#[derive(Clone)]
+pub struct OcspResponder {
+    issuer: Certificate,
+    key: SigningKey,
+    certificate: Option<Certificate>,
+    validity: Duration,
+    state: Arc<Mutex<ResponderState>>,
+}
+
+impl OcspResponder {
+    /// Create a responder signing with the key of the issuer.
+    pub fn new(issuer: &Certificate, key: SigningKey) -> OcspResponder {
+        OcspResponder {
+            issuer: issuer.clone(),
+            key,
+            certificate: None,
+            validity: Duration::days(1),
+            state: Default::default(),
+        }
+    }
+
+    /// Sign as a delegated responder instead, with the key of the given responder certificate.
+    /// The certificate is included in the responses.
+    pub fn with_responder_certificate(mut self, certificate: &Certificate) -> OcspResponder {
+        self.certificate = Some(certificate.clone());
+        self
+    }
+
+    /// Validity of responses to requests, between their this update and next update times.
+    pub fn with_validity(mut self, validity: Duration) -> OcspResponder {
+        self.validity = validity;
+        self
+    }
+
+    /// Set the status served for a certificate. Certificates without a status are unknown.
+    pub fn set_status(&self, certificate: &Certificate, status: RevocationStatus) {
+        if let Ok(mut state) = self.state.lock() {
+            state.statuses.retain(|(serial, _)| serial.as_slice() != certificate.serial());
+            state.statuses.push((certificate.serial().to_vec(), status));
+        }
+    }
+
+    /// Sign a response with the given status for a certificate of the issuer.
+    pub fn sign_response(&self, certificate: &Certificate, status: &RevocationStatus, produced_at: DateTime<Utc>, this_update: DateTime<Utc>, next_update: Option<DateTime<Utc>>) -> Result<OcspResponse, OcspError> {
+        let id = OcspCertificateId::new(certificate, &(self.issuer), OcspHashAlgorithm::Sha1)
+            .ok_or(OcspError::BadEncoding)?;
+
+        self.sign(&id, status, produced_at, this_update, next_update, None)
+    }
+
+    /// Answer a request at the given time with the status set for the certificate, echoing
+    /// its nonce. Requests for certificates of another issuer are answered as unknown.
+    pub fn respond(&self, request: &OcspRequest, time: DateTime<Utc>) -> Result<OcspResponse, OcspError> {
+        let id = request.id();
+
+        let expected = Issuer::from_certificate(&(self.issuer))
+            .and_then(|issuer| OcspCertificateId::from_serial(id.serial(), &issuer, id.hash_algorithm()));
+
+        let status = match expected {
+            Some(ref expected) if expected == id => self.state.lock().ok()
+                .and_then(|state| state.statuses.iter()
+                    .find(|(serial, _)| serial.as_slice() == id.serial())
+                    .map(|(_, status)| status.clone()))
+                .unwrap_or(RevocationStatus::Unknown),
+            _ => RevocationStatus::Unknown,
+        };
+
+        self.sign(id, &status, time, time, Some(time + self.validity), request.nonce())
+    }
+
+    /// Serve `OCSP` requests over `HTTP` on a background thread, as `POST` bodies or
+    /// base64 encoded in the path of `GET` requests. The listener stops when the returned
+    /// server is dropped.
+    pub fn serve(&self, address: impl ToSocketAddrs) -> IoResult<HttpServer> {
+        let responder = self.clone();
+        HttpServer::spawn(address, move |request| responder.handle(request))
+    }
+
+    fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let data = match request.method.as_str() {
+            "POST" => Some(request.body.clone()),
+            "GET" => BASE64.decode(request.path.trim_start_matches('/')).ok(),
+            _ => return HttpResponse::status(405),
+        };
+
+        let response = match data.as_deref().and_then(OcspRequest::parse) {
+            Some(request) => self.respond(&request, Utc::now())
+                .or_else(|_| OcspResponse::unsuccessful(OcspResponseStatus::InternalError).ok_or(())),
+            None => OcspResponse::unsuccessful(OcspResponseStatus::MalformedRequest).ok_or(()),
+        };
+
+        match response {
+            Ok(response) => HttpResponse::new("application/ocsp-response", response.raw_der_encoded().to_vec()),
+            Err(..) => HttpResponse::status(500),
+        }
+    }
+
+    fn sign(&self, id: &OcspCertificateId, status: &RevocationStatus, produced_at: DateTime<Utc>, this_update: DateTime<Utc>, next_update: Option<DateTime<Utc>>, nonce: Option<&[u8]>) -> Result<OcspResponse, OcspError> {
+        let status = match status {
+            RevocationStatus::Good => encoding::element(0x80, &[]),
+            RevocationStatus::Revoked(entry) => {
+                let mut revoked = encoding::generalized_time_element(entry.revocation_time());
+                if let Some(reason) = entry.reason() {
+                    revoked.extend(encoding::element(0xA0, encoding::element(0x0A, &[reason.code()]).as_slice()));
+                }
+
+                encoding::element(0xA1, revoked.as_slice())
+            },
+            RevocationStatus::Unknown => encoding::element(0x82, &[]),
+        };
+
+        let mut single = [
+            id.der_encoded(),
+            status,
+            encoding::generalized_time_element(this_update),
+        ].concat();
+
+        if let Some(next_update) = next_update {
+            single.extend(encoding::element(0xA0, encoding::generalized_time_element(next_update).as_slice()));
+        }
+
+        let responder = match self.certificate {
+            Some(ref certificate) => {
+                let key = ocsp::key_bits(certificate)
+                    .ok_or(OcspError::BadEncoding)?;
+
+                encoding::element(0xA2, encoding::element(0x04, OcspHashAlgorithm::Sha1.digest(key.as_slice()).as_slice()).as_slice())
+            },
+            None => {
+                let name = self.issuer.subject_der_encoded()
+                    .ok_or(OcspError::BadEncoding)?;
+
+                encoding::element(0xA1, name.as_slice())
+            },
+        };
+
+        let mut data = [
+            responder,
+            encoding::generalized_time_element(produced_at),
+            encoding::element(0x30, encoding::element(0x30, single.as_slice()).as_slice()),
+        ].concat();
+
+        if let Some(nonce) = nonce {
+            let extension = encoding::element(0x30, [
+                encoding::element(0x06, ocsp::NONCE_OID),
+                encoding::element(0x04, encoding::element(0x04, nonce).as_slice()),
+            ].concat().as_slice());
+
+            data.extend(encoding::element(0xA1, encoding::element(0x30, extension.as_slice()).as_slice()));
+        }
+
+        let data = encoding::element(0x30, data.as_slice());
+        let signature = self.key.sign(data.as_slice())?;
+
+        let mut basic = [
+            data,
+            self.key.algorithm_der_encoded(),
+            encoding::element(0x03, [&[0x00], signature.as_slice()].concat().as_slice()),
+        ].concat();
+
+        if let Some(ref certificate) = self.certificate {
+            basic.extend(encoding::element(0xA0, encoding::element(0x30, certificate.raw_der_encoded()).as_slice()));
+        }
+
+        let bytes = encoding::element(0x30, [
+            encoding::element(0x06, ocsp::BASIC_RESPONSE_OID),
+            encoding::element(0x04, encoding::element(0x30, basic.as_slice()).as_slice()),
+        ].concat().as_slice());
+
+        let response = encoding::element(0x30, [
+            encoding::element(0x0A, &[0x00]),
+            encoding::element(0xA0, bytes.as_slice()),
+        ].concat().as_slice());
+
+        OcspResponse::parse(response.as_slice())
+            .ok_or(OcspError::BadEncoding)
+    }
+}
@@ -0,0 +1,506 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+        Debug as FmtDebug,
+    },
+
+    error::{Error as StdError},
+    sync::{Arc},
+};
+
+use ring::{
+
+    digest::{
+
+        Algorithm as DigestAlgorithm,
+        SHA1_FOR_LEGACY_USE_ONLY,
+        SHA256,
+        SHA384,
+        SHA512,
+        digest,
+    },
+
+    signature::{
+
+        VerificationAlgorithm,
+        UnparsedPublicKey,
+        RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY,
+        RSA_PKCS1_2048_8192_SHA256,
+        RSA_PKCS1_2048_8192_SHA384,
+        RSA_PKCS1_2048_8192_SHA512,
+        RSA_PSS_2048_8192_SHA256,
+        RSA_PSS_2048_8192_SHA384,
+        RSA_PSS_2048_8192_SHA512,
+        ECDSA_P256_SHA256_ASN1,
+        ECDSA_P256_SHA384_ASN1,
+        ECDSA_P384_SHA256_ASN1,
+        ECDSA_P384_SHA384_ASN1,
+        ED25519,
+
+        ECDSA_P256_SHA256_ASN1_SIGNING,
+        ECDSA_P384_SHA384_ASN1_SIGNING,
+        RSA_PKCS1_SHA256,
+        EcdsaKeyPair,
+        Ed25519KeyPair,
+        RsaKeyPair,
+        KeyPair as RingKeyPair,
+    },
+
+    rand::{SystemRandom},
+};
+
+use x509_parser::pem::{Pem};
+
+use p521::ecdsa::{
+
+    signature::hazmat::{PrehashVerifier},
+
+    VerifyingKey as P521VerifyingKey,
+    Signature as P521Signature,
+};
+
+use crate::encoding::{self};
+
+/// Reason a signature could not be verified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The signed structure, public key or signature is not correctly encoded.
+    BadEncoding,
+    /// The signature algorithm, or its combination with the public key, is not supported.
+    UnsupportedAlgorithm,
+    /// The outer signature algorithm differs from the one in the signed structure, or the
+    /// signature algorithm is not one for the type of the public key.
+    AlgorithmMismatch,
+    /// The certificate does not carry a signature.
+    Unsigned,
+    /// The signature does not match the public key.
+    BadSignature,
+}
+
+impl FmtDisplay for SignatureError {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.write_str(match self {
+            SignatureError::BadEncoding => "signature or public key is not correctly encoded",
+            SignatureError::UnsupportedAlgorithm => "signature algorithm is not supported",
+            SignatureError::AlgorithmMismatch => "signature algorithms do not match",
+            SignatureError::Unsigned => "certificate is not signed",
+            SignatureError::BadSignature => "signature does not match the public key",
+        })
+    }
+}
+
+impl StdError for SignatureError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Hash {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl Hash {
+    fn from_oid(oid: &str) -> Option<Hash> {
+        match oid {
+            "1.3.14.3.2.26" => Some(Hash::Sha1),
+            "2.16.840.1.101.3.4.2.1" => Some(Hash::Sha256),
+            "2.16.840.1.101.3.4.2.2" => Some(Hash::Sha384),
+            "2.16.840.1.101.3.4.2.3" => Some(Hash::Sha512),
+            _ => None,
+        }
+    }
+
+    fn digest(&self) -> &'static DigestAlgorithm {
+        match self {
+            Hash::Sha1 => &SHA1_FOR_LEGACY_USE_ONLY,
+            Hash::Sha256 => &SHA256,
+            Hash::Sha384 => &SHA384,
+            Hash::Sha512 => &SHA512,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Scheme {
+    RsaPkcs1(Hash),
+    RsaPss(Hash),
+    Ecdsa(Hash),
+    Ed25519,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Rsa,
+    P256,
+    P384,
+    P521,
+    Ed25519,
+}
+
+/// Object identifier and raw parameters of a `DER` encoded algorithm identifier.
+pub(crate) fn algorithm_identifier(algorithm: &[u8]) -> Option<(String, Option<&[u8]>)> {
+    let contents = encoding::element_contents(algorithm, 0x30)?;
+    let (tag, oid, parameters) = encoding::read_element(contents)?;
+    if tag != 0x06 {
+        return None
+    }
+
+    let oid = encoding::oid_to_string(oid)?;
+    if parameters.is_empty() {
+        return Some((oid, None))
+    }
+
+    Some((oid, Some(parameters)))
+}
+
+fn pss_hash(parameters: &[u8]) -> Option<Hash> {
+    let mut parameters = encoding::element_contents(parameters, 0x30)?;
+
+    let mut hash = Hash::Sha1;
+    let mut mask = Hash::Sha1;
+    let mut salt = 20usize;
+
+    while !parameters.is_empty() {
+        let (tag, contents, remaining) = encoding::read_element(parameters)?;
+
+        match tag {
+            0xA0 => {
+                hash = Hash::from_oid(algorithm_identifier(contents)?.0.as_str())?;
+            },
+            0xA1 => {
+                let (oid, parameters) = algorithm_identifier(contents)?;
+                if oid != "1.2.840.113549.1.1.8" {
+                    return None
+                }
+
+                mask = Hash::from_oid(algorithm_identifier(parameters?)?.0.as_str())?;
+            },
+            0xA2 => {
+                let salt_length = encoding::element_contents(contents, 0x02)?;
+                if salt_length.len() > 4 {
+                    return None
+                }
+
+                salt = salt_length.iter().fold(0usize, |salt, octet| (salt << 8) | *octet as usize);
+            },
+            0xA3 => {
+                if encoding::element_contents(contents, 0x02)? != [0x01] {
+                    return None
+                }
+            },
+            _ => return None,
+        }
+
+        parameters = remaining;
+    }
+
+    if hash != mask || salt != hash.digest().output_len() {
+        return None
+    }
+
+    Some(hash)
+}
+
+fn scheme(algorithm: &[u8]) -> Option<Scheme> {
+    let (oid, parameters) = algorithm_identifier(algorithm)?;
+
+    match oid.as_str() {
+        "1.2.840.113549.1.1.5" => Some(Scheme::RsaPkcs1(Hash::Sha1)),
+        "1.2.840.113549.1.1.11" => Some(Scheme::RsaPkcs1(Hash::Sha256)),
+        "1.2.840.113549.1.1.12" => Some(Scheme::RsaPkcs1(Hash::Sha384)),
+        "1.2.840.113549.1.1.13" => Some(Scheme::RsaPkcs1(Hash::Sha512)),
+        "1.2.840.113549.1.1.10" => Some(Scheme::RsaPss(pss_hash(parameters?)?)),
+        "1.2.840.10045.4.3.2" => Some(Scheme::Ecdsa(Hash::Sha256)),
+        "1.2.840.10045.4.3.3" => Some(Scheme::Ecdsa(Hash::Sha384)),
+        "1.2.840.10045.4.3.4" => Some(Scheme::Ecdsa(Hash::Sha512)),
+        "1.3.101.112" => Some(Scheme::Ed25519),
+        _ => None,
+    }
+}
+
+/// Key type and raw key of a `DER` encoded subject public key info.
+fn public_key(public_key: &[u8]) -> Result<(Key, &[u8]), SignatureError> {
+    let contents = encoding::element_contents(public_key, 0x30)
+        .ok_or(SignatureError::BadEncoding)?;
+
+    let (tag, algorithm, remaining) = encoding::read_element(contents)
+        .ok_or(SignatureError::BadEncoding)?;
+
+    if tag != 0x30 {
+        return Err(SignatureError::BadEncoding)
+    }
+
+    let key = match encoding::element_contents(remaining, 0x03) {
+        Some([0x00, key @ ..]) => key,
+        _ => return Err(SignatureError::BadEncoding),
+    };
+
+    let algorithm = encoding::element(0x30, algorithm);
+    let (oid, parameters) = algorithm_identifier(algorithm.as_slice())
+        .ok_or(SignatureError::BadEncoding)?;
+
+    let curve = parameters.and_then(|parameters| encoding::element_contents(parameters, 0x06))
+        .and_then(encoding::oid_to_string);
+
+    match (oid.as_str(), curve.as_deref()) {
+        ("1.2.840.113549.1.1.1", _) |
+        ("1.2.840.113549.1.1.10", _) => Ok((Key::Rsa, key)),
+        ("1.2.840.10045.2.1", Some("1.2.840.10045.3.1.7")) => Ok((Key::P256, key)),
+        ("1.2.840.10045.2.1", Some("1.3.132.0.34")) => Ok((Key::P384, key)),
+        ("1.2.840.10045.2.1", Some("1.3.132.0.35")) => Ok((Key::P521, key)),
+        ("1.3.101.112", _) => Ok((Key::Ed25519, key)),
+        _ => Err(SignatureError::UnsupportedAlgorithm),
+    }
+}
+
+/// Verify a signature over a message, given the `DER` encoded subject public key info of
+/// the signer and the `DER` encoded algorithm identifier of the signature.
+pub(crate) fn verify(public_key_info: &[u8], algorithm: &[u8], message: &[u8], signature: &[u8]) -> Result<(), SignatureError> {
+    let scheme = scheme(algorithm).ok_or(SignatureError::UnsupportedAlgorithm)?;
+    let (key, public_key) = public_key(public_key_info)?;
+
+    let verification: &dyn VerificationAlgorithm = match (key, scheme) {
+        (Key::Rsa, Scheme::RsaPkcs1(Hash::Sha1)) => &RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY,
+        (Key::Rsa, Scheme::RsaPkcs1(Hash::Sha256)) => &RSA_PKCS1_2048_8192_SHA256,
+        (Key::Rsa, Scheme::RsaPkcs1(Hash::Sha384)) => &RSA_PKCS1_2048_8192_SHA384,
+        (Key::Rsa, Scheme::RsaPkcs1(Hash::Sha512)) => &RSA_PKCS1_2048_8192_SHA512,
+        (Key::Rsa, Scheme::RsaPss(Hash::Sha256)) => &RSA_PSS_2048_8192_SHA256,
+        (Key::Rsa, Scheme::RsaPss(Hash::Sha384)) => &RSA_PSS_2048_8192_SHA384,
+        (Key::Rsa, Scheme::RsaPss(Hash::Sha512)) => &RSA_PSS_2048_8192_SHA512,
+        (Key::P256, Scheme::Ecdsa(Hash::Sha256)) => &ECDSA_P256_SHA256_ASN1,
+        (Key::P256, Scheme::Ecdsa(Hash::Sha384)) => &ECDSA_P256_SHA384_ASN1,
+        (Key::P384, Scheme::Ecdsa(Hash::Sha256)) => &ECDSA_P384_SHA256_ASN1,
+        (Key::P384, Scheme::Ecdsa(Hash::Sha384)) => &ECDSA_P384_SHA384_ASN1,
+        (Key::Ed25519, Scheme::Ed25519) => &ED25519,
+        (Key::P521, Scheme::Ecdsa(hash)) => {
+            let key = P521VerifyingKey::from_sec1_bytes(public_key)
+                .map_err(|_| SignatureError::BadEncoding)?;
+
+            let signature = P521Signature::from_der(signature)
+                .map_err(|_| SignatureError::BadSignature)?;
+
+            let prehash = digest(hash.digest(), message);
+
+            return key.verify_prehash(prehash.as_ref(), &signature)
+                .map_err(|_| SignatureError::BadSignature)
+        },
+        (Key::Rsa, Scheme::RsaPss(..)) |
+        (Key::P256, Scheme::Ecdsa(..)) |
+        (Key::P384, Scheme::Ecdsa(..)) => return Err(SignatureError::UnsupportedAlgorithm),
+        _ => return Err(SignatureError::AlgorithmMismatch),
+    };
+
+    UnparsedPublicKey::new(verification, public_key)
+        .verify(message, signature)
+        .map_err(|_| SignatureError::BadSignature)
+}
+
+/// Signed data, signature algorithm and signature of a `DER` encoded signed structure,
+/// such as a certificate, revocation list or certificate request.
+pub(crate) fn split_signed(data: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    let contents = encoding::element_contents(data, 0x30)?;
+
+    let (_, _, remaining) = encoding::read_element(contents)?;
+    let signed = &(contents[..(contents.len() - remaining.len())]);
+
+    let (tag, _, signature) = encoding::read_element(remaining)?;
+    if tag != 0x30 {
+        return None
+    }
+
+    let algorithm = &(remaining[..(remaining.len() - signature.len())]);
+
+    match encoding::element_contents(signature, 0x03)? {
+        [0x00, signature @ ..] => Some((signed, algorithm, signature)),
+        _ => None,
+    }
+}
+
+/// Signature algorithm named inside a `DER` encoded to-be-signed certificate.
+pub(crate) fn tbs_algorithm(tbs: &[u8]) -> Option<&[u8]> {
+    let mut contents = encoding::element_contents(tbs, 0x30)?;
+
+    let (tag, _, remaining) = encoding::read_element(contents)?;
+    if tag == 0xA0 {
+        contents = remaining;
+    }
+
+    let (_, _, remaining) = encoding::read_element(contents)?;
+    let (tag, _, after) = encoding::read_element(remaining)?;
+    if tag != 0x30 {
+        return None
+    }
+
+    Some(&(remaining[..(remaining.len() - after.len())]))
+}
+
+/// Algorithm of a signing key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    /// `RSA` with `PKCS#1` v1.5 padding and SHA-256. Keys can be loaded, not generated.
+    Rsa,
+    /// `ECDSA` on P-256 with SHA-256.
+    EcdsaP256,
+    /// `ECDSA` on P-384 with SHA-384.
+    EcdsaP384,
+    Ed25519,
+}
+
+impl KeyAlgorithm {
+    /// `DER` encoded algorithm identifier of the signatures made with the algorithm.
+    pub fn der_encoded(&self) -> Vec<u8> {
+        let contents = match self {
+            KeyAlgorithm::Rsa => [
+                encoding::element(0x06, &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B]),
+                encoding::element(0x05, &[]),
+            ].concat(),
+            KeyAlgorithm::EcdsaP256 => encoding::element(0x06, &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02]),
+            KeyAlgorithm::EcdsaP384 => encoding::element(0x06, &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x03]),
+            KeyAlgorithm::Ed25519 => encoding::element(0x06, &[0x2B, 0x65, 0x70]),
+        };
+
+        encoding::element(0x30, contents.as_slice())
+    }
+}
+
+enum KeyPair {
+    Rsa(RsaKeyPair),
+    Ecdsa(EcdsaKeyPair, KeyAlgorithm),
+    Ed25519(Ed25519KeyPair),
+}
+
+/// Private key for signing certificates, requests, timestamps and responses.
+#[derive(Clone)]
+pub struct SigningKey {
+    pair: Arc<KeyPair>,
+    pkcs8: Vec<u8>,
+}
+
+impl SigningKey {
+    /// Load a key from a `DER` encoded `PKCS#8` private key.
+    pub fn from_pkcs8(data: &[u8]) -> Option<SigningKey> {
+        let random = SystemRandom::new();
+
+        let pair = if let Ok(pair) = RsaKeyPair::from_pkcs8(data) {
+            KeyPair::Rsa(pair)
+        }
+
+        else if let Ok(pair) = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, data, &random) {
+            KeyPair::Ecdsa(pair, KeyAlgorithm::EcdsaP256)
+        }
+
+        else if let Ok(pair) = EcdsaKeyPair::from_pkcs8(&ECDSA_P384_SHA384_ASN1_SIGNING, data, &random) {
+            KeyPair::Ecdsa(pair, KeyAlgorithm::EcdsaP384)
+        }
+
+        else if let Ok(pair) = Ed25519KeyPair::from_pkcs8_maybe_unchecked(data) {
+            KeyPair::Ed25519(pair)
+        }
+
+        else {
+
+            return None
+        };
+
+        Some(SigningKey { pair: Arc::new(pair), pkcs8: data.to_vec() })
+    }
+
+    /// Load a key from a `PEM` encoded `PKCS#8` private key.
+    pub fn from_pem(data: &[u8]) -> Option<SigningKey> {
+        let pem = Pem::iter_from_buffer(data).flatten()
+            .find(|pem| pem.label == "PRIVATE KEY")?;
+
+        SigningKey::from_pkcs8(pem.contents.as_slice())
+    }
+
+    /// Generate a new key. `RSA` keys cannot be generated.
+    pub fn generate(algorithm: KeyAlgorithm) -> Option<SigningKey> {
+        let random = SystemRandom::new();
+
+        let pkcs8 = match algorithm {
+            KeyAlgorithm::Rsa => return None,
+            KeyAlgorithm::EcdsaP256 => EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &random).ok()?,
+            KeyAlgorithm::EcdsaP384 => EcdsaKeyPair::generate_pkcs8(&ECDSA_P384_SHA384_ASN1_SIGNING, &random).ok()?,
+            KeyAlgorithm::Ed25519 => Ed25519KeyPair::generate_pkcs8(&random).ok()?,
+        };
+
+        SigningKey::from_pkcs8(pkcs8.as_ref())
+    }
+
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        match self.pair.as_ref() {
+            KeyPair::Rsa(..) => KeyAlgorithm::Rsa,
+            KeyPair::Ecdsa(_, algorithm) => *algorithm,
+            KeyPair::Ed25519(..) => KeyAlgorithm::Ed25519,
+        }
+    }
+
+    /// `DER` encoded `PKCS#8` private key.
+    pub fn pkcs8_der_encoded(&self) -> &[u8] {
+        self.pkcs8.as_slice()
+    }
+
+    /// `DER` encoded subject public key info of the key.
+    pub fn public_key_der_encoded(&self) -> Vec<u8> {
+        let (algorithm, key) = match self.pair.as_ref() {
+            KeyPair::Rsa(pair) => (vec![
+                encoding::element(0x06, &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01]),
+                encoding::element(0x05, &[]),
+            ], pair.public_key().as_ref()),
+            KeyPair::Ecdsa(pair, algorithm) => (vec![
+                encoding::element(0x06, &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01]),
+                match algorithm {
+                    KeyAlgorithm::EcdsaP384 => encoding::element(0x06, &[0x2B, 0x81, 0x04, 0x00, 0x22]),
+                    _ => encoding::element(0x06, &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07]),
+                },
+            ], pair.public_key().as_ref()),
+            KeyPair::Ed25519(pair) => (vec![
+                encoding::element(0x06, &[0x2B, 0x65, 0x70]),
+            ], pair.public_key().as_ref()),
+        };
+
+        let mut bits = vec![0x00];
+        bits.extend_from_slice(key);
+
+        let mut contents = encoding::element(0x30, algorithm.concat().as_slice());
+        contents.extend(encoding::element(0x03, bits.as_slice()));
+
+        encoding::element(0x30, contents.as_slice())
+    }
+
+    /// `DER` encoded algorithm identifier of the signatures made by the key.
+    pub fn algorithm_der_encoded(&self) -> Vec<u8> {
+        self.algorithm().der_encoded()
+    }
+
+    /// Sign a message with the algorithm of the key.
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SignatureError> {
+        let random = SystemRandom::new();
+
+        match self.pair.as_ref() {
+            KeyPair::Rsa(pair) => {
+                let mut signature = vec![0; pair.public().modulus_len()];
+                pair.sign(&RSA_PKCS1_SHA256, &random, message, signature.as_mut_slice())
+                    .map_err(|_| SignatureError::UnsupportedAlgorithm)?;
+
+                Ok(signature)
+            },
+            KeyPair::Ecdsa(pair, _) => pair.sign(&random, message)
+                .map(|signature| signature.as_ref().to_vec())
+                .map_err(|_| SignatureError::UnsupportedAlgorithm),
+            KeyPair::Ed25519(pair) => Ok(pair.sign(message).as_ref().to_vec()),
+        }
+    }
+}
+
+impl FmtDebug for SigningKey {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.debug_struct("SigningKey")
+            .field("algorithm", &(self.algorithm()))
+            .finish_non_exhaustive()
+    }
+}
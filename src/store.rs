@@ -0,0 +1,503 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+        Debug as FmtDebug,
+    },
+
+    path::{
+
+        PathBuf,
+        Path,
+    },
+
+    error::{Error as StdError},
+    sync::{Arc},
+    fs::{self},
+};
+
+use ring::digest::{
+
+    SHA1_FOR_LEGACY_USE_ONLY,
+    digest,
+};
+
+use rustls_native_certs::{Certificate as TlsNativeCertificate};
+
+use rustls::{
+
+    client::{WebPkiVerifier as TlsWebPkiVerifier},
+    server::{AllowAnyAuthenticatedClient as TlsAllowAnyAuthenticatedClient},
+
+    OwnedTrustAnchor as TlsOwnedTrustAnchor,
+    RootCertStore as TlsRootCertStore,
+};
+
+use tls_webpki::{TrustAnchor as TlsTrustAnchor};
+
+use webpki_roots::{TLS_SERVER_ROOTS};
+
+use x509_parser::pem::{Pem};
+
+use crate::{
+
+    certificate::{Certificate},
+    encoding::{self},
+};
+
+/// Reason a trust anchor source could not be loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrustStoreReason {
+    /// The file, directory or operating system store could not be read.
+    Read,
+    /// The `PEM` encoding is malformed.
+    Pem,
+    /// The `PEM` block holds something other than a certificate.
+    Unsupported,
+    /// The certificate could not be parsed.
+    Certificate,
+    /// The certificate is not usable as a trust anchor.
+    Anchor,
+    /// The hashed file name does not match the subject hash, the certificate is still loaded.
+    Hash,
+}
+
+impl FmtDisplay for TrustStoreReason {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.write_str(match self {
+            TrustStoreReason::Read => "could not be read",
+            TrustStoreReason::Pem => "malformed PEM encoding",
+            TrustStoreReason::Unsupported => "unsupported PEM label",
+            TrustStoreReason::Certificate => "certificate could not be parsed",
+            TrustStoreReason::Anchor => "certificate is not a valid trust anchor",
+            TrustStoreReason::Hash => "file name does not match the subject hash",
+        })
+    }
+}
+
+/// Problem with a single file, or entry within a bundle file or list of roots, while
+/// loading trust anchors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustStoreError {
+    path: Option<PathBuf>,
+    entry: Option<usize>,
+    reason: TrustStoreReason,
+}
+
+impl TrustStoreError {
+    fn new(path: &Path, entry: Option<usize>, reason: TrustStoreReason) -> TrustStoreError {
+        TrustStoreError { path: Some(path.to_path_buf()), entry, reason }
+    }
+
+    fn unnamed(entry: Option<usize>, reason: TrustStoreReason) -> TrustStoreError {
+        TrustStoreError { path: None, entry, reason }
+    }
+
+    /// File the trust anchors were loaded from, if any.
+    pub fn path(&self) -> Option<&Path> {
+        if let Some(ref path) = self.path {
+            return Some(path.as_path())
+        }
+
+        None
+    }
+
+    /// Index of the offending entry within a bundle file or list of roots.
+    pub fn entry(&self) -> Option<usize> {
+        self.entry
+    }
+
+    pub fn reason(&self) -> TrustStoreReason {
+        self.reason
+    }
+}
+
+impl FmtDisplay for TrustStoreError {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        match (self.path.as_ref(), self.entry) {
+            (Some(path), Some(entry)) => write!(formatter, "{} (entry {}): {}", path.display(), entry, self.reason),
+            (Some(path), None) => write!(formatter, "{}: {}", path.display(), self.reason),
+            (None, Some(entry)) => write!(formatter, "entry {}: {}", entry, self.reason),
+            (None, None) => write!(formatter, "{}", self.reason),
+        }
+    }
+}
+
+impl StdError for TrustStoreError {}
+
+/// `OpenSSL` hash of a `DER` encoded distinguished name, as used by `c_rehash`.
+pub(crate) fn name_hash(name: &[u8]) -> Option<u32> {
+    let mut canonical = Vec::new();
+    let mut sets = encoding::element_contents(name, 0x30)?;
+
+    while !sets.is_empty() {
+        let (tag, mut set, remaining) = encoding::read_element(sets)?;
+        if tag != 0x31 {
+            return None
+        }
+
+        let mut entries = Vec::new();
+
+        while !set.is_empty() {
+            let (tag, entry, remaining) = encoding::read_element(set)?;
+            if tag != 0x30 {
+                return None
+            }
+
+            let (tag, oid, value) = encoding::read_element(entry)?;
+            if tag != 0x06 {
+                return None
+            }
+
+            let mut encoded = encoding::element(0x06, oid);
+            encoded.extend(canonical_value(value)?);
+            entries.push(encoding::element(0x30, encoded.as_slice()));
+
+            set = remaining;
+        }
+
+        entries.sort();
+
+        canonical.extend(encoding::element(0x31, entries.concat().as_slice()));
+        sets = remaining;
+    }
+
+    let hash = digest(&SHA1_FOR_LEGACY_USE_ONLY, canonical.as_slice());
+    let hash = hash.as_ref();
+
+    Some(u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]))
+}
+
+fn canonical_value(value: &[u8]) -> Option<Vec<u8>> {
+    let (tag, contents, remaining) = encoding::read_element(value)?;
+
+    let text: String = match tag {
+        0x0C => String::from_utf8(contents.to_vec()).ok()?,
+        0x13 | 0x14 | 0x16 | 0x1A => contents.iter().map(|octet| *octet as char).collect(),
+        0x1C => contents.chunks(4).map(|chunk| match chunk {
+            [a, b, c, d] => char::from_u32(u32::from_be_bytes([*a, *b, *c, *d])),
+            _ => None,
+        }).collect::<Option<String>>()?,
+        0x1E => contents.chunks(2).map(|chunk| match chunk {
+            [a, b] => char::from_u32(u16::from_be_bytes([*a, *b]) as u32),
+            _ => None,
+        }).collect::<Option<String>>()?,
+        _ => return Some(value[..(value.len() - remaining.len())].to_vec()),
+    };
+
+    let mut canonical = String::with_capacity(text.len());
+
+    for word in text.split(|character: char| character.is_ascii_whitespace() || character == '\x0B')
+        .filter(|word| !word.is_empty()) {
+
+        if !canonical.is_empty() {
+            canonical.push(' ');
+        }
+
+        canonical.push_str(word.to_ascii_lowercase().as_str());
+    }
+
+    Some(encoding::element(0x0C, canonical.as_bytes()))
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct TrustAnchor {
+    pub(crate) subject: Vec<u8>,
+    pub(crate) spki: Vec<u8>,
+    pub(crate) name_constraints: Option<Vec<u8>>,
+    pub(crate) certificate: Option<Certificate>,
+}
+
+impl TrustAnchor {
+    pub(crate) fn from_certificate(certificate: &Certificate) -> Option<TrustAnchor> {
+        if let Certificate::Signed(..) = certificate {
+            if let Ok(anchor) = TlsTrustAnchor::try_from_cert_der(certificate.raw_der_encoded()) {
+                return Some(TrustAnchor {
+                    subject: anchor.subject.to_vec(),
+                    spki: anchor.spki.to_vec(),
+                    name_constraints: anchor.name_constraints.map(|constraints| constraints.to_vec()),
+                    certificate: Some(certificate.clone()),
+                })
+            }
+        }
+
+        None
+    }
+
+    /// Whether the anchor is the issuer named by a `DER` encoded distinguished name.
+    pub(crate) fn is_named(&self, name: &[u8]) -> bool {
+        if let Some(name) = encoding::element_contents(name, 0x30) {
+            return name == self.subject.as_slice()
+        }
+
+        false
+    }
+
+    fn is_same(&self, other: &TrustAnchor) -> bool {
+        self.subject == other.subject && self.spki == other.spki && self.name_constraints == other.name_constraints
+    }
+}
+
+/// Set of trust anchors that is built once and shared between verifications.
+///
+/// Cloning is cheap, the anchors and the verifiers built from them are shared.
+#[derive(Clone)]
+pub struct TrustStore {
+    anchors: Arc<Vec<TrustAnchor>>,
+    pub(crate) server: Arc<TlsWebPkiVerifier>,
+    pub(crate) client: Arc<TlsAllowAnyAuthenticatedClient>,
+}
+
+impl TrustStore {
+    pub(crate) fn from_anchors(anchors: Vec<TrustAnchor>) -> TrustStore {
+        let mut trust_store = TlsRootCertStore::empty();
+
+        trust_store.add_trust_anchors(anchors.iter().map(|anchor| {
+            TlsOwnedTrustAnchor::from_subject_spki_name_constraints(anchor.subject.as_slice(),
+                anchor.spki.as_slice(), anchor.name_constraints.as_deref())
+        }));
+
+        TrustStore {
+            anchors: Arc::new(anchors),
+            server: Arc::new(TlsWebPkiVerifier::new(trust_store.clone(), None)),
+            client: Arc::new(TlsAllowAnyAuthenticatedClient::new(trust_store)),
+        }
+    }
+
+    /// Trust store with the bundled web roots.
+    pub fn web_roots() -> TrustStore {
+        TrustStore::from_anchors(TLS_SERVER_ROOTS.0.iter().map(|anchor| TrustAnchor {
+            subject: anchor.subject.to_vec(),
+            spki: anchor.spki.to_vec(),
+            name_constraints: anchor.name_constraints.map(|constraints| constraints.to_vec()),
+            certificate: None,
+        }).collect())
+    }
+
+    /// Trust store with the roots of the operating system, reporting every root that could
+    /// not be loaded by its position in the system store.
+    pub fn system_roots() -> (TrustStore, Vec<TrustStoreError>) {
+        let mut anchors = Vec::new();
+        let mut errors = Vec::new();
+
+        match rustls_native_certs::load_native_certs() {
+            Ok(native) => load_native(native.as_slice(), &mut anchors, &mut errors),
+            Err(..) => errors.push(TrustStoreError::unnamed(None, TrustStoreReason::Read)),
+        }
+
+        (TrustStore::from_anchors(anchors), errors)
+    }
+
+    /// Trust store with the given roots, failing if any of them is not a valid trust anchor.
+    pub fn from_certificates(roots: &[Certificate]) -> Result<TrustStore, TrustStoreError> {
+        let mut anchors = Vec::with_capacity(roots.len());
+
+        for (entry, root) in roots.iter().enumerate() {
+            anchors.push(TrustAnchor::from_certificate(root)
+                .ok_or_else(|| TrustStoreError::unnamed(Some(entry), TrustStoreReason::Anchor))?);
+        }
+
+        Ok(TrustStore::from_anchors(anchors))
+    }
+
+    /// Trust store with the roots from `PEM` encoded data, failing on the first entry that
+    /// cannot be loaded. Entries other than certificates are skipped.
+    pub fn from_pem(data: &[u8]) -> Result<TrustStore, TrustStoreError> {
+        let mut anchors = Vec::new();
+
+        for (entry, pem) in Pem::iter_from_buffer(data).enumerate() {
+            let pem = pem.map_err(|_| TrustStoreError::unnamed(Some(entry), TrustStoreReason::Pem))?;
+            if pem.label != "CERTIFICATE" {
+                continue
+            }
+
+            let root = Certificate::parse(pem.contents.as_slice())
+                .ok_or_else(|| TrustStoreError::unnamed(Some(entry), TrustStoreReason::Certificate))?;
+
+            anchors.push(TrustAnchor::from_certificate(&root)
+                .ok_or_else(|| TrustStoreError::unnamed(Some(entry), TrustStoreReason::Anchor))?);
+        }
+
+        Ok(TrustStore::from_anchors(anchors))
+    }
+
+    /// Trust store with the roots from a `PEM` encoded file, failing on the first entry that
+    /// cannot be loaded.
+    pub fn from_pem_file(path: impl AsRef<Path>) -> Result<TrustStore, TrustStoreError> {
+        let path = path.as_ref();
+
+        let data = fs::read(path)
+            .map_err(|_| TrustStoreError::new(path, None, TrustStoreReason::Read))?;
+
+        TrustStore::from_pem(data.as_slice())
+            .map_err(|error| TrustStoreError { path: Some(path.to_path_buf()), ..error })
+    }
+
+    /// Trust store with the roots from a bundle file such as `ca-certificates.crt`,
+    /// reporting every entry that could not be loaded.
+    pub fn from_bundle_file(path: impl AsRef<Path>) -> (TrustStore, Vec<TrustStoreError>) {
+        let mut anchors = Vec::new();
+        let mut errors = Vec::new();
+
+        load_file(path.as_ref(), None, &mut anchors, &mut errors);
+
+        (TrustStore::from_anchors(anchors), errors)
+    }
+
+    /// Trust store with the roots from a directory such as `/etc/ssl/certs`, including the
+    /// `<subject_hash>.N` layout written by `c_rehash`, reporting every file that could not be loaded.
+    pub fn from_directory(path: impl AsRef<Path>) -> (TrustStore, Vec<TrustStoreError>) {
+        let mut anchors = Vec::new();
+        let mut errors = Vec::new();
+
+        let entries = match fs::read_dir(path.as_ref()) {
+            Ok(entries) => entries,
+            Err(..) => {
+                errors.push(TrustStoreError::new(path.as_ref(), None, TrustStoreReason::Read));
+                return (TrustStore::from_anchors(anchors), errors)
+            },
+        };
+
+        let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+
+        paths.sort();
+
+        for path in paths.iter() {
+            let name = path.file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+
+            if name.starts_with('.') {
+                continue
+            }
+
+            let hash = match name.split_once('.') {
+                Some((hash, index)) if hash.len() == 8 => {
+                    if index.starts_with('r') && index[1..].parse::<u32>().is_ok() {
+                        continue
+                    }
+
+                    if index.parse::<u32>().is_ok() {
+                        u32::from_str_radix(hash, 16).ok()
+                    } else { None }
+                },
+                _ => None,
+            };
+
+            load_file(path.as_path(), hash, &mut anchors, &mut errors);
+        }
+
+        (TrustStore::from_anchors(anchors), errors)
+    }
+
+    /// Trust store with the anchors of both stores.
+    pub fn merge(&self, other: &TrustStore) -> TrustStore {
+        let mut anchors = self.anchors.as_ref().clone();
+
+        for anchor in other.anchors.iter() {
+            if anchors.iter().any(|existing| existing.is_same(anchor)) {
+                continue
+            }
+
+            anchors.push(anchor.clone());
+        }
+
+        TrustStore::from_anchors(anchors)
+    }
+
+    pub(crate) fn anchors(&self) -> &[TrustAnchor] {
+        self.anchors.as_slice()
+    }
+
+    /// Roots of the trust store that are known as certificates.
+    pub fn certificates(&self) -> Vec<Certificate> {
+        self.anchors.iter()
+            .filter_map(|anchor| anchor.certificate.clone())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+}
+
+fn load_certificate(path: &Path, data: &[u8], entry: Option<usize>, hash: Option<u32>, anchors: &mut Vec<TrustAnchor>, errors: &mut Vec<TrustStoreError>) {
+    let certificate = match Certificate::parse(data) {
+        Some(certificate @ Certificate::Signed(..)) => certificate,
+        _ => return errors.push(TrustStoreError::new(path, entry, TrustStoreReason::Certificate)),
+    };
+
+    let anchor = match TrustAnchor::from_certificate(&certificate) {
+        Some(anchor) => anchor,
+        None => return errors.push(TrustStoreError::new(path, entry, TrustStoreReason::Anchor)),
+    };
+
+    if let Some(hash) = hash {
+        if certificate.subject_hash() != Some(hash) {
+            errors.push(TrustStoreError::new(path, entry, TrustStoreReason::Hash));
+        }
+    }
+
+    if !anchors.iter().any(|existing| existing.is_same(&anchor)) {
+        anchors.push(anchor);
+    }
+}
+
+fn load_file(path: &Path, hash: Option<u32>, anchors: &mut Vec<TrustAnchor>, errors: &mut Vec<TrustStoreError>) {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(..) => return errors.push(TrustStoreError::new(path, None, TrustStoreReason::Read)),
+    };
+
+    if !data.windows(11).any(|window| window == b"-----BEGIN ") {
+        return load_certificate(path, data.as_slice(), None, hash, anchors, errors)
+    }
+
+    for (entry, pem) in Pem::iter_from_buffer(data.as_slice()).enumerate() {
+        match pem {
+            Ok(pem) if pem.label == "CERTIFICATE" || pem.label == "TRUSTED CERTIFICATE" => {
+                load_certificate(path, pem.contents.as_slice(), Some(entry), hash, anchors, errors)
+            },
+            Ok(..) => errors.push(TrustStoreError::new(path, Some(entry), TrustStoreReason::Unsupported)),
+            Err(..) => {
+                errors.push(TrustStoreError::new(path, Some(entry), TrustStoreReason::Pem));
+                break
+            },
+        }
+    }
+}
+
+pub(crate) fn load_native(native: &[TlsNativeCertificate], anchors: &mut Vec<TrustAnchor>, errors: &mut Vec<TrustStoreError>) {
+    for (entry, TlsNativeCertificate(data)) in native.iter().enumerate() {
+        let root = match Certificate::parse(data) {
+            Some(root) => root,
+            None => {
+                errors.push(TrustStoreError::unnamed(Some(entry), TrustStoreReason::Certificate));
+                continue
+            },
+        };
+
+        match TrustAnchor::from_certificate(&root) {
+            Some(anchor) => anchors.push(anchor),
+            None => errors.push(TrustStoreError::unnamed(Some(entry), TrustStoreReason::Anchor)),
+        }
+    }
+}
+
+impl FmtDebug for TrustStore {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.debug_struct("TrustStore")
+            .field("anchors", &(self.len()))
+            .finish()
+    }
+}
@@ -0,0 +1,191 @@
+use base64::{
+
+    engine::general_purpose::{STANDARD as BASE64},
+    Engine,
+};
+
+use chrono::{
+
+    DateTime,
+    TimeZone,
+    Utc,
+};
+
+use serde::{Deserialize};
+
+use crate::{
+
+    transparency::{
+
+        Reader,
+
+        merkle::{self},
+    },
+    certificate::{Certificate},
+};
+
+/// Kind of certificate logged in a log entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogEntryType {
+    X509,
+    Precert,
+}
+
+/// Certificate transparency log entry, decoded from a `MerkleTreeLeaf` and its extra data.
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    timestamp: u64,
+    entry_type: LogEntryType,
+    certificate: Certificate,
+    issuer_key_hash: Option<[u8; 32]>,
+    extensions: Vec<u8>,
+    precertificate: Option<Certificate>,
+    chain: Vec<Certificate>,
+    leaf_input: Vec<u8>,
+    extra_data: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct RawEntries {
+    entries: Vec<RawEntry>,
+}
+
+#[derive(Deserialize)]
+struct RawEntry {
+    leaf_input: String,
+    extra_data: String,
+}
+
+fn certificate_chain(data: &[u8]) -> Option<Vec<Certificate>> {
+    let mut reader = Reader::new(data);
+    let mut chain = Vec::new();
+
+    while !reader.is_empty() {
+        chain.push(Certificate::parse(reader.vector(3)?)?);
+    }
+
+    Some(chain)
+}
+
+type TimestampedEntry = (u64, LogEntryType, Certificate, Option<[u8; 32]>, Vec<u8>);
+
+/// Read a `TimestampedEntry`, giving its timestamp, type, certificate, issuer key hash and extensions.
+pub(crate) fn timestamped_entry(reader: &mut Reader<'_>) -> Option<TimestampedEntry> {
+    let timestamp = reader.number(8)?;
+    let (entry_type, certificate, issuer_key_hash) = match reader.number(2)? {
+        0 => (LogEntryType::X509, Certificate::parse(reader.vector(3)?)?, None),
+        1 => {
+            let issuer_key_hash = reader.bytes(32)?.try_into().ok()?;
+            (LogEntryType::Precert, Certificate::parse(reader.vector(3)?)?, Some(issuer_key_hash))
+        },
+        _ => return None,
+    };
+
+    let extensions = reader.vector(2)?.to_vec();
+    Some((timestamp, entry_type, certificate, issuer_key_hash, extensions))
+}
+
+impl LogEntry {
+    /// Decode a `MerkleTreeLeaf` and the extra data logged with it, as returned by `get-entries`.
+    pub fn parse(leaf_input: &[u8], extra_data: &[u8]) -> Option<LogEntry> {
+        let mut reader = Reader::new(leaf_input);
+
+        // Version 1 with a timestamped entry leaf.
+        if reader.number(1)? != 0 || reader.number(1)? != 0 {
+            return None
+        }
+
+        let (timestamp, entry_type, certificate, issuer_key_hash, extensions) = timestamped_entry(&mut reader)?;
+        if !reader.is_empty() {
+            return None
+        }
+
+        let mut reader = Reader::new(extra_data);
+        let precertificate = match entry_type {
+            LogEntryType::Precert => Some(Certificate::parse(reader.vector(3)?)?),
+            LogEntryType::X509 => None,
+        };
+
+        let chain = certificate_chain(reader.vector(3)?)?;
+        if !reader.is_empty() {
+            return None
+        }
+
+        Some(LogEntry {
+            timestamp,
+            entry_type,
+            certificate,
+            issuer_key_hash,
+            extensions,
+            precertificate,
+            chain,
+            leaf_input: leaf_input.to_vec(),
+            extra_data: extra_data.to_vec(),
+        })
+    }
+
+    /// Decode the entries of a `get-entries` `JSON` response.
+    pub fn from_json(data: &[u8]) -> Option<Vec<LogEntry>> {
+        let entries: RawEntries = serde_json::from_slice(data).ok()?;
+
+        entries.entries.into_iter().map(|entry| {
+            let leaf_input = BASE64.decode(entry.leaf_input).ok()?;
+            let extra_data = BASE64.decode(entry.extra_data).ok()?;
+
+            LogEntry::parse(leaf_input.as_slice(), extra_data.as_slice())
+        }).collect()
+    }
+
+    /// Milliseconds since the Unix epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(self.timestamp as i64).single().unwrap_or_default()
+    }
+
+    pub fn entry_type(&self) -> LogEntryType {
+        self.entry_type
+    }
+
+    /// Logged certificate, pending with the final TBS for precertificate entries.
+    pub fn certificate(&self) -> &Certificate {
+        &(self.certificate)
+    }
+
+    /// SHA-256 hash of the issuer public key, for precertificate entries.
+    pub fn issuer_key_hash(&self) -> Option<&[u8]> {
+        self.issuer_key_hash.as_ref()
+            .map(|hash| hash.as_slice())
+    }
+
+    pub fn extensions(&self) -> &[u8] {
+        self.extensions.as_slice()
+    }
+
+    /// Signed precertificate submitted to the log, for precertificate entries.
+    pub fn precertificate(&self) -> Option<&Certificate> {
+        self.precertificate.as_ref()
+    }
+
+    /// Chain submitted with the certificate, starting with its issuer.
+    pub fn chain(&self) -> &[Certificate] {
+        self.chain.as_slice()
+    }
+
+    /// Encoded `MerkleTreeLeaf`.
+    pub fn leaf_input(&self) -> &[u8] {
+        self.leaf_input.as_slice()
+    }
+
+    /// Encoded extra data logged with the certificate.
+    pub fn extra_data(&self) -> &[u8] {
+        self.extra_data.as_slice()
+    }
+
+    /// Merkle tree hash of the leaf.
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        merkle::leaf_hash(self.leaf_input.as_slice())
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+    },
+
+    path::{Path},
+    fs::{self},
+};
+
+use base64::{
+
+    engine::general_purpose::{STANDARD as BASE64},
+    Engine,
+};
+
+use chrono::{
+
+    DateTime,
+    Utc,
+};
+
+use serde::{Deserialize};
+
+use ring::digest::{
+
+    SHA256,
+    digest,
+};
+
+use crate::encoding::{self};
+
+/// Public key of a certificate transparency log, identified by the SHA-256 hash of its
+/// `DER` encoded subject public key info.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogKey {
+    id: [u8; 32],
+    public_key: Vec<u8>,
+}
+
+impl LogKey {
+    /// Create a log key from a `DER` encoded subject public key info.
+    pub fn new(public_key: &[u8]) -> Option<LogKey> {
+        let (tag, _, rest) = encoding::read_element(public_key)?;
+        if tag != 0x30 || !rest.is_empty() {
+            return None
+        }
+
+        let id = digest(&SHA256, public_key).as_ref()
+            .try_into().ok()?;
+
+        Some(LogKey { id, public_key: public_key.to_vec() })
+    }
+
+    pub fn id(&self) -> &[u8] {
+        &(self.id)
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        self.public_key.as_slice()
+    }
+}
+
+/// State of a log in a log list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CtLogState {
+    Pending,
+    Qualified,
+    Usable,
+    ReadOnly,
+    Retired,
+    Rejected,
+}
+
+/// Period in which the certificates logged to a log must expire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CtLogInterval {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+impl CtLogInterval {
+    /// Inclusive start of the interval.
+    pub fn start(&self) -> DateTime<Utc> {
+        self.start
+    }
+
+    /// Exclusive end of the interval.
+    pub fn end(&self) -> DateTime<Utc> {
+        self.end
+    }
+
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+/// Certificate transparency log described by a log list.
+#[derive(Clone, Debug)]
+pub struct CtLog {
+    operator: String,
+    description: String,
+    key: LogKey,
+    url: String,
+    monitoring_url: Option<String>,
+    mmd: Option<u64>,
+    state: Option<(CtLogState, DateTime<Utc>)>,
+    interval: Option<CtLogInterval>,
+}
+
+impl CtLog {
+    pub fn operator(&self) -> &str {
+        self.operator.as_str()
+    }
+
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    /// Short name of the log, the quoted part of its description when present.
+    pub fn name(&self) -> &str {
+        let mut parts = self.description.split('\'');
+        if let (Some(_), Some(name), Some(_)) = (parts.next(), parts.next(), parts.next()) {
+            return name
+        }
+
+        self.description.as_str()
+    }
+
+    pub fn id(&self) -> &[u8] {
+        self.key.id()
+    }
+
+    pub fn key(&self) -> &LogKey {
+        &(self.key)
+    }
+
+    /// Submission `URL` of the log.
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Monitoring `URL` of a tiled log.
+    pub fn monitoring_url(&self) -> Option<&str> {
+        self.monitoring_url.as_deref()
+    }
+
+    pub fn is_tiled(&self) -> bool {
+        self.monitoring_url.is_some()
+    }
+
+    /// Maximum merge delay in seconds.
+    pub fn mmd(&self) -> Option<u64> {
+        self.mmd
+    }
+
+    pub fn state(&self) -> Option<CtLogState> {
+        self.state.map(|(state, _)| state)
+    }
+
+    /// Time the log entered its current state.
+    pub fn state_time(&self) -> Option<DateTime<Utc>> {
+        self.state.map(|(_, time)| time)
+    }
+
+    pub fn interval(&self) -> Option<CtLogInterval> {
+        self.interval
+    }
+}
+
+impl FmtDisplay for CtLog {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        write!(formatter, "{} {}", self.operator, self.name())
+    }
+}
+
+#[derive(Deserialize)]
+struct RawLogList {
+    version: Option<String>,
+    log_list_timestamp: Option<DateTime<Utc>>,
+    operators: Vec<RawOperator>,
+}
+
+#[derive(Deserialize)]
+struct RawOperator {
+    name: String,
+    #[serde(default)]
+    logs: Vec<RawLog>,
+    #[serde(default)]
+    tiled_logs: Vec<RawLog>,
+}
+
+#[derive(Deserialize)]
+struct RawLog {
+    description: Option<String>,
+    log_id: String,
+    key: String,
+    url: Option<String>,
+    submission_url: Option<String>,
+    monitoring_url: Option<String>,
+    mmd: Option<u64>,
+    state: Option<RawState>,
+    temporal_interval: Option<RawInterval>,
+}
+
+#[derive(Deserialize)]
+struct RawState {
+    pending: Option<RawStateTime>,
+    qualified: Option<RawStateTime>,
+    usable: Option<RawStateTime>,
+    readonly: Option<RawStateTime>,
+    retired: Option<RawStateTime>,
+    rejected: Option<RawStateTime>,
+}
+
+#[derive(Deserialize)]
+struct RawStateTime {
+    timestamp: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct RawInterval {
+    start_inclusive: DateTime<Utc>,
+    end_exclusive: DateTime<Utc>,
+}
+
+impl RawState {
+    fn state(self) -> Option<(CtLogState, DateTime<Utc>)> {
+        [
+            (CtLogState::Pending, self.pending),
+            (CtLogState::Qualified, self.qualified),
+            (CtLogState::Usable, self.usable),
+            (CtLogState::ReadOnly, self.readonly),
+            (CtLogState::Retired, self.retired),
+            (CtLogState::Rejected, self.rejected),
+        ].into_iter().find_map(|(state, time)| {
+            time.map(|time| (state, time.timestamp))
+        })
+    }
+}
+
+impl RawLog {
+    fn log(self, operator: &str) -> Option<CtLog> {
+        let key = LogKey::new(BASE64.decode(self.key).ok()?.as_slice())?;
+        if key.id() != BASE64.decode(self.log_id).ok()?.as_slice() {
+            return None
+        }
+
+        Some(CtLog {
+            operator: operator.to_owned(),
+            description: self.description.unwrap_or_default(),
+            url: self.url.or(self.submission_url)?,
+            monitoring_url: self.monitoring_url,
+            mmd: self.mmd,
+            state: self.state.and_then(RawState::state),
+            interval: self.temporal_interval.map(|interval| CtLogInterval {
+                start: interval.start_inclusive,
+                end: interval.end_exclusive,
+            }),
+            key,
+        })
+    }
+}
+
+/// Certificate transparency log list, in the version 3 `JSON` format published by Google and Apple.
+#[derive(Clone, Debug)]
+pub struct CtLogList {
+    version: Option<String>,
+    timestamp: Option<DateTime<Utc>>,
+    logs: Vec<CtLog>,
+}
+
+impl CtLogList {
+    /// Parse a log list from `JSON`. Logs whose key does not match their log `ID` are skipped.
+    pub fn from_json(data: &[u8]) -> Option<CtLogList> {
+        let list: RawLogList = serde_json::from_slice(data).ok()?;
+
+        let mut logs = Vec::new();
+        for operator in list.operators {
+            let name = operator.name;
+            logs.extend(operator.logs.into_iter().chain(operator.tiled_logs)
+                .filter_map(|log| log.log(name.as_str())));
+        }
+
+        Some(CtLogList {
+            version: list.version,
+            timestamp: list.log_list_timestamp,
+            logs,
+        })
+    }
+
+    /// Load a log list from a `JSON` file.
+    pub fn from_file(path: impl AsRef<Path>) -> Option<CtLogList> {
+        CtLogList::from_json(fs::read(path).ok()?.as_slice())
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.timestamp
+    }
+
+    pub fn logs(&self) -> &[CtLog] {
+        self.logs.as_slice()
+    }
+
+    /// Log with the given log `ID`.
+    pub fn find(&self, id: &[u8]) -> Option<&CtLog> {
+        self.logs.iter().find(|log| log.id() == id)
+    }
+
+    /// Keys of all logs in the list, for verifying signed certificate timestamps.
+    pub fn keys(&self) -> Vec<LogKey> {
+        self.logs.iter().map(|log| log.key.clone()).collect()
+    }
+}
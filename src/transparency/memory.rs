@@ -0,0 +1,368 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+    },
+
+    error::{Error as StdError},
+    io::{Result as IoResult},
+    net::{ToSocketAddrs},
+    sync::{Arc, Mutex},
+};
+
+use base64::{
+
+    engine::general_purpose::{STANDARD as BASE64},
+    Engine,
+};
+
+use chrono::{
+
+    DateTime,
+    Utc,
+};
+
+use serde_json::{json};
+
+use crate::{
+
+    transparency::{
+
+        SignedCertificateTimestamp,
+        ConsistencyProof,
+        SignedTreeHead,
+        InclusionProof,
+        TimestampError,
+        MerkleTree,
+        LogEntry,
+        LogKey,
+
+        timestamp::{self},
+        merkle::{self},
+    },
+
+    http::{
+
+        HttpResponse,
+        HttpRequest,
+        HttpServer,
+    },
+
+    certificate::{Certificate},
+    signature::{SigningKey},
+};
+
+/// Reason a submission to a [`MemoryLog`] was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryLogError {
+    EmptyChain,
+    /// A precertificate was submitted as a certificate.
+    Precertificate,
+    /// A certificate was submitted as a precertificate.
+    NotPrecertificate,
+    /// A precertificate was submitted without its issuer.
+    MissingIssuer,
+    /// A certificate in the chain is not signed by the next one.
+    BadChain,
+    /// A certificate is not signed or not correctly encoded.
+    BadEncoding,
+    /// The log could not sign with its key.
+    Signing,
+}
+
+impl FmtDisplay for MemoryLogError {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.write_str(match self {
+            MemoryLogError::EmptyChain => "submitted chain is empty",
+            MemoryLogError::Precertificate => "submitted certificate is a precertificate",
+            MemoryLogError::NotPrecertificate => "submitted certificate is not a precertificate",
+            MemoryLogError::MissingIssuer => "submitted precertificate has no issuer",
+            MemoryLogError::BadChain => "submitted chain is not correctly signed",
+            MemoryLogError::BadEncoding => "submitted certificate is not correctly encoded",
+            MemoryLogError::Signing => "log could not sign with its key",
+        })
+    }
+}
+
+impl StdError for MemoryLogError {}
+
+impl From<TimestampError> for MemoryLogError {
+    fn from(error: TimestampError) -> MemoryLogError {
+        match error {
+            TimestampError::BadEncoding => MemoryLogError::BadEncoding,
+            _ => MemoryLogError::Signing,
+        }
+    }
+}
+
+struct MemoryEntry {
+    entry_type: u16,
+    entry: Vec<u8>,
+    leaf_input: Vec<u8>,
+    extra_data: Vec<u8>,
+    timestamp: SignedCertificateTimestamp,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    entries: Vec<MemoryEntry>,
+    tree: MerkleTree,
+}
+
+/// In-process certificate transparency log for tests. Submissions are incorporated into the
+/// tree immediately, and chains are checked for signatures but not against accepted roots.
+#[derive(Clone)]
+pub struct MemoryLog {
+    key: SigningKey,
+    log_key: LogKey,
+    state: Arc<Mutex<MemoryState>>,
+}
+
+fn chain_data(chain: &[Certificate]) -> Result<Vec<u8>, TimestampError> {
+    let mut certificates = Vec::new();
+    for certificate in chain {
+        timestamp::push_vector(&mut certificates, 3, certificate.raw_der_encoded())?;
+    }
+
+    let mut data = Vec::new();
+    timestamp::push_vector(&mut data, 3, certificates.as_slice())?;
+
+    Ok(data)
+}
+
+fn check_chain(chain: &[Certificate]) -> Result<(), MemoryLogError> {
+    if chain.iter().any(|certificate| matches!(certificate, Certificate::Pending(..))) {
+        return Err(MemoryLogError::BadEncoding)
+    }
+
+    for pair in chain.windows(2) {
+        if pair[0].verify_signed_by(&(pair[1])).is_err() {
+            return Err(MemoryLogError::BadChain)
+        }
+    }
+
+    Ok(())
+}
+
+impl MemoryLog {
+    /// Create an empty log signing with the given key.
+    pub fn new(key: SigningKey) -> Option<MemoryLog> {
+        let log_key = LogKey::new(key.public_key_der_encoded().as_slice())?;
+        Some(MemoryLog { key, log_key, state: Default::default() })
+    }
+
+    /// Key for verifying timestamps and tree heads issued by the log.
+    pub fn log_key(&self) -> &LogKey {
+        &(self.log_key)
+    }
+
+    /// Number of entries in the log.
+    pub fn len(&self) -> u64 {
+        self.state.lock().map(|state| state.tree.len())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn add(&self, entry_type: u16, entry: Vec<u8>, extra_data: Vec<u8>, time: DateTime<Utc>) -> Result<SignedCertificateTimestamp, MemoryLogError> {
+        let mut state = self.state.lock()
+            .map_err(|_| MemoryLogError::Signing)?;
+
+        if let Some(existing) = state.entries.iter().find(|existing| existing.entry_type == entry_type && existing.entry == entry) {
+            return Ok(existing.timestamp.clone())
+        }
+
+        let time = time.timestamp_millis() as u64;
+        let timestamp = SignedCertificateTimestamp::sign(&(self.key), time, entry_type, entry.as_slice())?;
+
+        let mut leaf_input = vec![0, 0];
+        leaf_input.extend_from_slice(&(time.to_be_bytes()));
+        leaf_input.extend_from_slice(&(entry_type.to_be_bytes()));
+        leaf_input.extend_from_slice(entry.as_slice());
+        timestamp::push_vector(&mut leaf_input, 2, &[])?;
+
+        state.tree.push(merkle::leaf_hash(leaf_input.as_slice()));
+        state.entries.push(MemoryEntry {
+            entry_type,
+            entry,
+            leaf_input,
+            extra_data,
+            timestamp: timestamp.clone(),
+        });
+
+        Ok(timestamp)
+    }
+
+    /// Log a certificate with its chain, as `add-chain`, giving its signed certificate timestamp.
+    pub fn add_chain(&self, chain: &[Certificate], time: DateTime<Utc>) -> Result<SignedCertificateTimestamp, MemoryLogError> {
+        let (certificate, issuers) = chain.split_first()
+            .ok_or(MemoryLogError::EmptyChain)?;
+
+        if certificate.is_precertificate() {
+            return Err(MemoryLogError::Precertificate)
+        }
+
+        check_chain(chain)?;
+
+        let entry = timestamp::x509_entry(certificate)?;
+        self.add(0, entry, chain_data(issuers)?, time)
+    }
+
+    /// Log a precertificate with its chain, starting with its issuer, as `add-pre-chain`,
+    /// giving its signed certificate timestamp. Precertificate signing certificates are not supported.
+    pub fn add_pre_chain(&self, chain: &[Certificate], time: DateTime<Utc>) -> Result<SignedCertificateTimestamp, MemoryLogError> {
+        let (precertificate, issuers) = chain.split_first()
+            .ok_or(MemoryLogError::EmptyChain)?;
+
+        if !precertificate.is_precertificate() {
+            return Err(MemoryLogError::NotPrecertificate)
+        }
+
+        let issuer = issuers.first()
+            .ok_or(MemoryLogError::MissingIssuer)?;
+
+        check_chain(chain)?;
+
+        let entry = timestamp::precert_entry(precertificate, issuer)?;
+
+        let mut extra_data = Vec::new();
+        timestamp::push_vector(&mut extra_data, 3, precertificate.raw_der_encoded())?;
+        extra_data.extend(chain_data(issuers)?);
+
+        self.add(1, entry, extra_data, time)
+    }
+
+    /// Signed tree head over all entries, as `get-sth`.
+    pub fn get_sth(&self, time: DateTime<Utc>) -> Result<SignedTreeHead, MemoryLogError> {
+        let state = self.state.lock()
+            .map_err(|_| MemoryLogError::Signing)?;
+
+        let root_hash = state.tree.root_hash(state.tree.len())
+            .ok_or(MemoryLogError::Signing)?;
+
+        SignedTreeHead::sign(&(self.key), time.timestamp_millis() as u64, state.tree.len(), root_hash)
+            .map_err(MemoryLogError::from)
+    }
+
+    /// Entries from the first to the last index, inclusive, as `get-entries`.
+    pub fn get_entries(&self, start: u64, end: u64) -> Vec<LogEntry> {
+        let state = match self.state.lock() {
+            Ok(state) => state,
+            Err(..) => return Vec::new(),
+        };
+
+        state.entries.iter()
+            .skip(start as usize)
+            .take((end.saturating_sub(start) as usize).saturating_add(1))
+            .filter_map(|entry| LogEntry::parse(entry.leaf_input.as_slice(), entry.extra_data.as_slice()))
+            .collect()
+    }
+
+    /// Inclusion proof of the leaf with the given hash in the tree of the given size, as `get-proof-by-hash`.
+    pub fn get_proof_by_hash(&self, leaf_hash: &[u8], size: u64) -> Option<InclusionProof> {
+        let state = self.state.lock().ok()?;
+
+        let index = (0..size.min(state.tree.len()))
+            .find(|index| state.tree.leaf_hash(*index) == Some(leaf_hash))?;
+
+        state.tree.inclusion_proof(index, size)
+    }
+
+    /// Consistency proof between the trees of the given sizes, as `get-sth-consistency`.
+    pub fn get_sth_consistency(&self, first: u64, second: u64) -> Option<ConsistencyProof> {
+        self.state.lock().ok()?
+            .tree.consistency_proof(first, second)
+    }
+
+    /// Serve the log over `HTTP` at the given address, under the `/ct/v1/` paths of RFC 6962.
+    pub fn serve(&self, address: impl ToSocketAddrs) -> IoResult<HttpServer> {
+        let log = self.clone();
+        HttpServer::spawn(address, move |request| log.respond(request))
+    }
+
+    fn respond(&self, request: &HttpRequest) -> HttpResponse {
+        let number = |name: &str| request.parameter(name)
+            .and_then(|value| value.parse::<u64>().ok());
+
+        let body = match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/ct/v1/add-chain") |
+            ("POST", "/ct/v1/add-pre-chain") => {
+                let chain = serde_json::from_slice::<serde_json::Value>(request.body.as_slice()).ok()
+                    .and_then(|body| body.get("chain")?.as_array()?.iter().map(|certificate| {
+                        Certificate::parse(BASE64.decode(certificate.as_str()?).ok()?.as_slice())
+                    }).collect::<Option<Vec<Certificate>>>());
+
+                let chain = match chain {
+                    Some(chain) => chain,
+                    None => return HttpResponse::status(400),
+                };
+
+                let timestamp = match request.path.as_str() {
+                    "/ct/v1/add-chain" => self.add_chain(chain.as_slice(), Utc::now()),
+                    _ => self.add_pre_chain(chain.as_slice(), Utc::now()),
+                };
+
+                match timestamp {
+                    Ok(timestamp) => json!({
+                        "sct_version": 0,
+                        "id": BASE64.encode(timestamp.log_id()),
+                        "timestamp": timestamp.timestamp(),
+                        "extensions": BASE64.encode(timestamp.extensions()),
+                        "signature": BASE64.encode(timestamp.digitally_signed_encoded()),
+                    }),
+                    Err(..) => return HttpResponse::status(400),
+                }
+            },
+            ("GET", "/ct/v1/get-sth") => match self.get_sth(Utc::now()) {
+                Ok(head) => json!({
+                    "tree_size": head.size(),
+                    "timestamp": head.timestamp(),
+                    "sha256_root_hash": BASE64.encode(head.root_hash()),
+                    "tree_head_signature": BASE64.encode(head.signature()),
+                }),
+                Err(..) => return HttpResponse::status(500),
+            },
+            ("GET", "/ct/v1/get-entries") => match (number("start"), number("end")) {
+                (Some(start), Some(end)) if start <= end => json!({
+                    "entries": self.get_entries(start, end).iter().map(|entry| json!({
+                        "leaf_input": BASE64.encode(entry.leaf_input()),
+                        "extra_data": BASE64.encode(entry.extra_data()),
+                    })).collect::<Vec<_>>(),
+                }),
+                _ => return HttpResponse::status(400),
+            },
+            ("GET", "/ct/v1/get-proof-by-hash") => {
+                let hash = request.parameter("hash")
+                    .and_then(|hash| BASE64.decode(hash).ok());
+
+                match (hash, number("tree_size")) {
+                    (Some(hash), Some(size)) => match self.get_proof_by_hash(hash.as_slice(), size) {
+                        Some(proof) => json!({
+                            "leaf_index": proof.index(),
+                            "audit_path": proof.path().iter().map(|node| BASE64.encode(node)).collect::<Vec<_>>(),
+                        }),
+                        None => return HttpResponse::status(404),
+                    },
+                    _ => return HttpResponse::status(400),
+                }
+            },
+            ("GET", "/ct/v1/get-sth-consistency") => match (number("first"), number("second")) {
+                (Some(first), Some(second)) => match self.get_sth_consistency(first, second) {
+                    Some(proof) => json!({
+                        "consistency": proof.path().iter().map(|node| BASE64.encode(node)).collect::<Vec<_>>(),
+                    }),
+                    None => return HttpResponse::status(400),
+                },
+                _ => return HttpResponse::status(400),
+            },
+            _ => return HttpResponse::status(404),
+        };
+
+        HttpResponse::new("application/json", body.to_string().into_bytes())
+    }
+}
@@ -0,0 +1,467 @@
+use base64::{
+
+    engine::general_purpose::{STANDARD as BASE64},
+    Engine,
+};
+
+use chrono::{
+
+    DateTime,
+    TimeZone,
+    Utc,
+};
+
+use ring::digest::{
+
+    Context as DigestContext,
+    SHA256,
+};
+
+use serde::{Deserialize};
+
+use crate::{
+
+    transparency::{
+
+        TimestampSignatureAlgorithm,
+        TimestampHashAlgorithm,
+        TimestampError,
+        LogKey,
+        Reader,
+
+        timestamp::{self},
+    },
+
+    signature::{SigningKey, self},
+};
+
+fn hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut context = DigestContext::new(&SHA256);
+    for part in parts {
+        context.update(part);
+    }
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(context.finish().as_ref());
+    hash
+}
+
+/// Hash of a Merkle tree leaf with the given encoded `MerkleTreeLeaf`.
+pub(crate) fn leaf_hash(leaf_input: &[u8]) -> [u8; 32] {
+    hash(&[&[0x00], leaf_input])
+}
+
+fn node_hash(left: &[u8], right: &[u8]) -> [u8; 32] {
+    hash(&[&[0x01], left, right])
+}
+
+/// Largest power of two smaller than the size.
+fn split(size: usize) -> usize {
+    let mut split = 1;
+    while split << 1 < size {
+        split <<= 1;
+    }
+
+    split
+}
+
+/// Root hash of the tree with the given leaf hashes.
+fn tree_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves.len() {
+        0 => hash(&[]),
+        1 => leaves[0],
+        size => {
+            let (left, right) = leaves.split_at(split(size));
+            node_hash(&(tree_root(left)), &(tree_root(right)))
+        },
+    }
+}
+
+/// Audit path of the leaf at the index.
+fn inclusion_path(index: usize, leaves: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    if leaves.len() <= 1 {
+        return Vec::new()
+    }
+
+    let (left, right) = leaves.split_at(split(leaves.len()));
+    if index < left.len() {
+        let mut path = inclusion_path(index, left);
+        path.push(tree_root(right));
+        path
+    }
+
+    else {
+
+        let mut path = inclusion_path(index - left.len(), right);
+        path.push(tree_root(left));
+        path
+    }
+}
+
+/// Consistency proof between the tree of the first leaves and the tree of all leaves.
+fn consistency_path(first: usize, leaves: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    fn subproof(first: usize, leaves: &[[u8; 32]], complete: bool) -> Vec<[u8; 32]> {
+        if first == leaves.len() {
+            return if complete { Vec::new() } else { vec![tree_root(leaves)] }
+        }
+
+        let (left, right) = leaves.split_at(split(leaves.len()));
+        if first <= left.len() {
+            let mut path = subproof(first, left, complete);
+            path.push(tree_root(right));
+            path
+        }
+
+        else {
+
+            let mut path = subproof(first - left.len(), right, false);
+            path.push(tree_root(left));
+            path
+        }
+    }
+
+    if first == 0 || first >= leaves.len() {
+        return Vec::new()
+    }
+
+    subproof(first, leaves, true)
+}
+
+/// Merkle tree over leaf hashes, for computing root hashes and proofs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MerkleTree {
+    leaves: Vec<[u8; 32]>,
+}
+
+impl MerkleTree {
+    pub fn new() -> MerkleTree {
+        Default::default()
+    }
+
+    /// Create a tree from encoded `MerkleTreeLeaf` structures.
+    pub fn from_leaf_inputs<'a>(leaf_inputs: impl IntoIterator<Item = &'a [u8]>) -> MerkleTree {
+        MerkleTree { leaves: leaf_inputs.into_iter().map(leaf_hash).collect() }
+    }
+
+    /// Append a leaf hash, giving the index of the leaf.
+    pub fn push(&mut self, leaf_hash: [u8; 32]) -> u64 {
+        self.leaves.push(leaf_hash);
+        self.leaves.len() as u64 - 1
+    }
+
+    pub fn len(&self) -> u64 {
+        self.leaves.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn leaf_hash(&self, index: u64) -> Option<&[u8]> {
+        self.leaves.get(index as usize)
+            .map(|hash| hash.as_slice())
+    }
+
+    /// Root hash of the tree of the given size, a prefix of this tree.
+    pub fn root_hash(&self, size: u64) -> Option<[u8; 32]> {
+        let leaves = self.leaves.get(..size as usize)?;
+        Some(tree_root(leaves))
+    }
+
+    /// Inclusion proof of the leaf at the index in the tree of the given size.
+    pub fn inclusion_proof(&self, index: u64, size: u64) -> Option<InclusionProof> {
+        let leaves = self.leaves.get(..size as usize)?;
+        if index >= size {
+            return None
+        }
+
+        Some(InclusionProof::new(index, size, inclusion_path(index as usize, leaves)))
+    }
+
+    /// Consistency proof between the trees of the given sizes.
+    pub fn consistency_proof(&self, first: u64, second: u64) -> Option<ConsistencyProof> {
+        let leaves = self.leaves.get(..second as usize)?;
+        if first > second {
+            return None
+        }
+
+        Some(ConsistencyProof::new(first, second, consistency_path(first as usize, leaves)))
+    }
+}
+
+fn decode_hashes(hashes: Vec<String>) -> Option<Vec<[u8; 32]>> {
+    hashes.into_iter().map(|hash| {
+        BASE64.decode(hash).ok()?.try_into().ok()
+    }).collect()
+}
+
+/// Data covered by a version 1 tree head signature.
+fn tree_head_data(timestamp: u64, size: u64, root_hash: &[u8]) -> Vec<u8> {
+    let mut message = vec![0, 1];
+    message.extend_from_slice(&(timestamp.to_be_bytes()));
+    message.extend_from_slice(&(size.to_be_bytes()));
+    message.extend_from_slice(root_hash);
+    message
+}
+
+/// Verify a version 1 tree head signature, given its `digitally-signed` encoding.
+pub(crate) fn verify_tree_head(key: &LogKey, timestamp: u64, size: u64, root_hash: &[u8], signature: &[u8]) -> Result<(), TimestampError> {
+    let mut reader = Reader::new(signature);
+    let hash_algorithm = reader.number(1).ok_or(TimestampError::BadEncoding)?;
+    let signature_algorithm = reader.number(1).ok_or(TimestampError::BadEncoding)?;
+    let value = reader.vector(2).ok_or(TimestampError::BadEncoding)?;
+
+    if !reader.is_empty() {
+        return Err(TimestampError::BadEncoding)
+    }
+
+    let algorithm = timestamp::algorithm_identifier(TimestampHashAlgorithm::from_code(hash_algorithm as u8),
+        TimestampSignatureAlgorithm::from_code(signature_algorithm as u8))
+        .ok_or(TimestampError::UnsupportedAlgorithm)?;
+
+    let message = tree_head_data(timestamp, size, root_hash);
+
+    signature::verify(key.public_key(), algorithm.as_slice(), message.as_slice(), value)
+        .map_err(TimestampError::from)
+}
+
+/// Signed tree head of a log, as returned by `get-sth`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedTreeHead {
+    size: u64,
+    timestamp: u64,
+    root_hash: [u8; 32],
+    signature: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct RawTreeHead {
+    tree_size: u64,
+    timestamp: u64,
+    sha256_root_hash: String,
+    tree_head_signature: String,
+}
+
+impl SignedTreeHead {
+    /// Sign a tree head with the key of a log.
+    pub(crate) fn sign(key: &SigningKey, timestamp: u64, size: u64, root_hash: [u8; 32]) -> Result<SignedTreeHead, TimestampError> {
+        let signature = key.sign(tree_head_data(timestamp, size, &(root_hash)).as_slice())?;
+        let signature = timestamp::digitally_signed(key, signature.as_slice())?;
+
+        Ok(SignedTreeHead { size, timestamp, root_hash, signature })
+    }
+
+    /// Decode a `get-sth` `JSON` response.
+    pub fn from_json(data: &[u8]) -> Option<SignedTreeHead> {
+        let head: RawTreeHead = serde_json::from_slice(data).ok()?;
+
+        Some(SignedTreeHead {
+            size: head.tree_size,
+            timestamp: head.timestamp,
+            root_hash: BASE64.decode(head.sha256_root_hash).ok()?.try_into().ok()?,
+            signature: BASE64.decode(head.tree_head_signature).ok()?,
+        })
+    }
+
+    /// Number of entries in the tree.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Milliseconds since the Unix epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(self.timestamp as i64).single().unwrap_or_default()
+    }
+
+    pub fn root_hash(&self) -> &[u8] {
+        &(self.root_hash)
+    }
+
+    /// Encoded `digitally-signed` tree head signature.
+    pub fn signature(&self) -> &[u8] {
+        self.signature.as_slice()
+    }
+
+    /// Verify the tree head signature with the key of the log.
+    pub fn verify(&self, key: &LogKey) -> Result<(), TimestampError> {
+        verify_tree_head(key, self.timestamp, self.size, &(self.root_hash), self.signature.as_slice())
+    }
+}
+
+/// Proof that a leaf is included in a tree of a given size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InclusionProof {
+    index: u64,
+    size: u64,
+    path: Vec<[u8; 32]>,
+}
+
+#[derive(Deserialize)]
+struct RawInclusionProof {
+    leaf_index: u64,
+    audit_path: Vec<String>,
+}
+
+impl InclusionProof {
+    pub fn new(index: u64, size: u64, path: Vec<[u8; 32]>) -> InclusionProof {
+        InclusionProof { index, size, path }
+    }
+
+    /// Decode a `get-proof-by-hash` `JSON` response for a tree of the given size.
+    pub fn from_json(data: &[u8], size: u64) -> Option<InclusionProof> {
+        let proof: RawInclusionProof = serde_json::from_slice(data).ok()?;
+        Some(InclusionProof::new(proof.leaf_index, size, decode_hashes(proof.audit_path)?))
+    }
+
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn path(&self) -> &[[u8; 32]] {
+        self.path.as_slice()
+    }
+
+    /// Verify that the leaf with the given hash is included in the tree with the given root hash.
+    pub fn verify(&self, leaf_hash: &[u8], root_hash: &[u8]) -> bool {
+        if self.index >= self.size {
+            return false
+        }
+
+        let (mut index, mut last) = (self.index, self.size - 1);
+        let mut hash: [u8; 32] = match leaf_hash.try_into() {
+            Ok(hash) => hash,
+            Err(..) => return false,
+        };
+
+        for node in self.path.iter() {
+            if last == 0 {
+                return false
+            }
+
+            if index & 1 == 1 || index == last {
+                hash = node_hash(node, &(hash));
+                while index & 1 == 0 && index != 0 {
+                    index >>= 1;
+                    last >>= 1;
+                }
+            }
+
+            else {
+
+                hash = node_hash(&(hash), node);
+            }
+
+            index >>= 1;
+            last >>= 1;
+        }
+
+        last == 0 && hash.as_slice() == root_hash
+    }
+}
+
+/// Proof that a tree of a given size is a prefix of a larger tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsistencyProof {
+    first: u64,
+    second: u64,
+    path: Vec<[u8; 32]>,
+}
+
+#[derive(Deserialize)]
+struct RawConsistencyProof {
+    consistency: Vec<String>,
+}
+
+impl ConsistencyProof {
+    pub fn new(first: u64, second: u64, path: Vec<[u8; 32]>) -> ConsistencyProof {
+        ConsistencyProof { first, second, path }
+    }
+
+    /// Decode a `get-sth-consistency` `JSON` response between trees of the given sizes.
+    pub fn from_json(data: &[u8], first: u64, second: u64) -> Option<ConsistencyProof> {
+        let proof: RawConsistencyProof = serde_json::from_slice(data).ok()?;
+        Some(ConsistencyProof::new(first, second, decode_hashes(proof.consistency)?))
+    }
+
+    pub fn first(&self) -> u64 {
+        self.first
+    }
+
+    pub fn second(&self) -> u64 {
+        self.second
+    }
+
+    pub fn path(&self) -> &[[u8; 32]] {
+        self.path.as_slice()
+    }
+
+    /// Verify that the tree with the first root hash is a prefix of the tree with the second root hash.
+    pub fn verify(&self, first_root: &[u8], second_root: &[u8]) -> bool {
+        if self.first > self.second {
+            return false
+        }
+
+        if self.first == self.second {
+            return self.path.is_empty() && first_root == second_root
+        }
+
+        if self.first == 0 {
+            return self.path.is_empty()
+        }
+
+        let first_root: [u8; 32] = match first_root.try_into() {
+            Ok(hash) => hash,
+            Err(..) => return false,
+        };
+
+        let mut path = self.path.clone();
+        if self.first.is_power_of_two() {
+            path.insert(0, first_root);
+        }
+
+        let (mut index, mut last) = (self.first - 1, self.second - 1);
+        while index & 1 == 1 {
+            index >>= 1;
+            last >>= 1;
+        }
+
+        let (seed, path) = match path.split_first() {
+            Some(split) => split,
+            None => return false,
+        };
+
+        let (mut first, mut second) = (*seed, *seed);
+        for node in path {
+            if last == 0 {
+                return false
+            }
+
+            if index & 1 == 1 || index == last {
+                first = node_hash(node, &(first));
+                second = node_hash(node, &(second));
+                while index & 1 == 0 && index != 0 {
+                    index >>= 1;
+                    last >>= 1;
+                }
+            }
+
+            else {
+
+                second = node_hash(&(second), node);
+            }
+
+            index >>= 1;
+            last >>= 1;
+        }
+
+        last == 0 && first == first_root && second.as_slice() == second_root
+    }
+}
@@ -0,0 +1,210 @@
+pub mod timestamp;
+pub use timestamp::{
+
+    SignedCertificateTimestamp,
+    TimestampVerification,
+    TimestampSignatureAlgorithm,
+    TimestampHashAlgorithm,
+    TimestampVersion,
+    TimestampError,
+};
+
+pub mod log;
+pub use log::{
+
+    CtLogInterval,
+    CtLogState,
+    CtLogList,
+    LogKey,
+    CtLog,
+};
+
+pub mod policy;
+pub use policy::{
+
+    CtTimestampExclusion,
+    CtPolicyFailure,
+    CtPolicyReport,
+    CtPolicy,
+};
+
+pub mod entry;
+pub use entry::{
+
+    LogEntryType,
+    LogEntry,
+};
+
+pub mod tile;
+pub use tile::{
+
+    NoteSignature,
+    Checkpoint,
+    TileLeaf,
+};
+
+pub mod merkle;
+pub use merkle::{
+
+    ConsistencyProof,
+    SignedTreeHead,
+    InclusionProof,
+    MerkleTree,
+};
+
+pub mod memory;
+pub use memory::{
+
+    MemoryLogError,
+    MemoryLog,
+};
+
+use crate::encoding::{self};
+
+/// `OBJECT IDENTIFIER` contents of the precertificate poison extension (1.3.6.1.4.1.11129.2.4.3).
+pub(crate) const POISON_OID: &[u8] = &[0x2B, 0x06, 0x01, 0x04, 0x01, 0xD6, 0x79, 0x02, 0x04, 0x03];
+
+/// `OBJECT IDENTIFIER` contents of the embedded signed certificate timestamp list extension (1.3.6.1.4.1.11129.2.4.2).
+pub(crate) const TIMESTAMP_LIST_OID: &[u8] = &[0x2B, 0x06, 0x01, 0x04, 0x01, 0xD6, 0x79, 0x02, 0x04, 0x02];
+
+/// Extensions of a `DER` encoded TBS certificate, as pairs of `OBJECT IDENTIFIER`
+/// contents and the complete extension element.
+pub(crate) fn extensions(tbs: &[u8]) -> Option<Vec<(&[u8], &[u8])>> {
+    let mut fields = encoding::element_contents(tbs, 0x30)?;
+    let mut extensions = Vec::new();
+
+    while !fields.is_empty() {
+        let (tag, contents, rest) = encoding::read_element(fields)?;
+        fields = rest;
+
+        if tag != 0xA3 {
+            continue
+        }
+
+        let mut remaining = encoding::element_contents(contents, 0x30)?;
+        while !remaining.is_empty() {
+            let (_, extension, rest) = encoding::read_element(remaining)?;
+            let element = &(remaining[..remaining.len() - rest.len()]);
+            let oid = encoding::element_contents(extension, 0x06)?;
+
+            extensions.push((oid, element));
+            remaining = rest;
+        }
+    }
+
+    Some(extensions)
+}
+
+/// Contents of the `OCTET STRING` value of the extension with the given `OBJECT IDENTIFIER` contents.
+pub(crate) fn extension_value<'a>(tbs: &'a [u8], oid: &[u8]) -> Option<&'a [u8]> {
+    let (_, element) = extensions(tbs)?.into_iter()
+        .find(|(candidate, _)| *candidate == oid)?;
+
+    let mut fields = encoding::element_contents(element, 0x30)?;
+    while !fields.is_empty() {
+        let (tag, contents, rest) = encoding::read_element(fields)?;
+        if tag == 0x04 {
+            return Some(contents)
+        }
+
+        fields = rest;
+    }
+
+    None
+}
+
+/// Whether a `DER` encoded extension element is marked critical.
+pub(crate) fn is_critical(element: &[u8]) -> bool {
+    let fields = encoding::element_contents(element, 0x30)
+        .and_then(encoding::read_element)
+        .and_then(|(_, _, fields)| encoding::read_element(fields));
+
+    matches!(fields, Some((0x01, [0xFF], _)))
+}
+
+/// Re-encode a `DER` encoded TBS certificate without the extensions with the given
+/// `OBJECT IDENTIFIER` contents. The extensions field is omitted when none remain.
+pub(crate) fn remove_extensions(tbs: &[u8], oids: &[&[u8]]) -> Option<Vec<u8>> {
+    let mut fields = encoding::element_contents(tbs, 0x30)?;
+    let mut encoded = Vec::new();
+
+    while !fields.is_empty() {
+        let (tag, contents, rest) = encoding::read_element(fields)?;
+        let element = &(fields[..fields.len() - rest.len()]);
+        fields = rest;
+
+        if tag != 0xA3 {
+            encoded.extend_from_slice(element);
+            continue
+        }
+
+        let mut retained = Vec::new();
+        let mut remaining = encoding::element_contents(contents, 0x30)?;
+
+        while !remaining.is_empty() {
+            let (_, extension, rest) = encoding::read_element(remaining)?;
+            let element = &(remaining[..remaining.len() - rest.len()]);
+            let oid = encoding::element_contents(extension, 0x06)?;
+
+            if !oids.contains(&oid) {
+                retained.extend_from_slice(element);
+            }
+
+            remaining = rest;
+        }
+
+        if !retained.is_empty() {
+            encoded.extend(encoding::element(0xA3, encoding::element(0x30, retained.as_slice()).as_slice()));
+        }
+    }
+
+    Some(encoding::element(0x30, encoded.as_slice()))
+}
+
+/// The RFC 6962 final TBS: the TBS without the embedded timestamp list and the poison.
+pub(crate) fn final_tbs(tbs: &[u8]) -> Option<Vec<u8>> {
+    remove_extensions(tbs, &[TIMESTAMP_LIST_OID, POISON_OID])
+}
+
+/// Reader for TLS presentation language structures, as used by RFC 6962.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Reader<'a> {
+        Reader { data }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Data that has not been read yet.
+    pub(crate) fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub(crate) fn bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        if self.data.len() < length {
+            return None
+        }
+
+        let (bytes, rest) = self.data.split_at(length);
+        self.data = rest;
+
+        Some(bytes)
+    }
+
+    /// Unsigned big endian integer of the given width in bytes.
+    pub(crate) fn number(&mut self, width: usize) -> Option<u64> {
+        let bytes = self.bytes(width)?;
+        Some(bytes.iter().fold(0u64, |value, octet| (value << 8) | *octet as u64))
+    }
+
+    /// Variable length vector prefixed by a length of the given width in bytes.
+    pub(crate) fn vector(&mut self, width: usize) -> Option<&'a [u8]> {
+        let length = self.number(width)? as usize;
+        self.bytes(length)
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+
+    collections::{HashSet},
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+    },
+};
+
+use chrono::{
+
+    Duration,
+    DateTime,
+    Utc,
+};
+
+use crate::{
+
+    transparency::{
+
+        SignedCertificateTimestamp,
+        TimestampError,
+        CtLogState,
+        CtLogList,
+    },
+
+    certificate::{Certificate},
+};
+
+/// Certificate transparency policy of a browser vendor, for embedded signed certificate timestamps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CtPolicy {
+    Chrome,
+    Apple,
+}
+
+impl CtPolicy {
+    /// Timestamps required from logs that are qualified, usable or read-only at the time of check.
+    fn required_current(&self) -> usize {
+        match self {
+            CtPolicy::Chrome => 1,
+            CtPolicy::Apple => 2,
+        }
+    }
+}
+
+impl FmtDisplay for CtPolicy {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.write_str(match self {
+            CtPolicy::Chrome => "Chrome",
+            CtPolicy::Apple => "Apple",
+        })
+    }
+}
+
+/// Reason a signed certificate timestamp does not count towards a policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CtTimestampExclusion {
+    /// The log is not in the log list.
+    UnknownLog,
+    /// The timestamp signature could not be verified.
+    Invalid(TimestampError),
+    /// The timestamp lies after the time of check.
+    FutureTimestamp,
+    /// The log was pending, rejected, not yet qualified or usable, or has no state when the
+    /// timestamp was issued.
+    NotQualified,
+    /// The log was retired before the timestamp was issued.
+    Retired,
+    /// The certificate expires outside the temporal interval of the log.
+    OutsideInterval,
+}
+
+impl FmtDisplay for CtTimestampExclusion {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        match self {
+            CtTimestampExclusion::UnknownLog => formatter.write_str("log is not in the log list"),
+            CtTimestampExclusion::Invalid(error) => write!(formatter, "{}", error),
+            CtTimestampExclusion::FutureTimestamp => formatter.write_str("timestamp lies in the future"),
+            CtTimestampExclusion::NotQualified => formatter.write_str("log is not qualified"),
+            CtTimestampExclusion::Retired => formatter.write_str("log was retired before the timestamp"),
+            CtTimestampExclusion::OutsideInterval => formatter.write_str("certificate expires outside the temporal interval of the log"),
+        }
+    }
+}
+
+/// Requirement of a policy that was not met.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CtPolicyFailure {
+    /// Too few timestamps from once or currently qualified logs for the certificate lifetime.
+    TooFewTimestamps { required: usize, found: usize },
+    /// Too few timestamps from logs that are qualified, usable or read-only at the time of check.
+    TooFewCurrentTimestamps { required: usize, found: usize },
+    /// Too few distinct log operators among the counted timestamps.
+    TooFewOperators { required: usize, found: usize },
+}
+
+impl FmtDisplay for CtPolicyFailure {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        match self {
+            CtPolicyFailure::TooFewTimestamps { required, found } => {
+                write!(formatter, "{} qualified timestamps required, {} found", required, found)
+            },
+            CtPolicyFailure::TooFewCurrentTimestamps { required, found } => {
+                write!(formatter, "{} timestamps from currently qualified logs required, {} found", required, found)
+            },
+            CtPolicyFailure::TooFewOperators { required, found } => {
+                write!(formatter, "{} distinct log operators required, {} found", required, found)
+            },
+        }
+    }
+}
+
+/// Outcome of evaluating a certificate against a certificate transparency policy.
+#[derive(Clone, Debug)]
+pub struct CtPolicyReport {
+    policy: CtPolicy,
+    timestamps: Vec<(SignedCertificateTimestamp, Option<CtTimestampExclusion>)>,
+    failures: Vec<CtPolicyFailure>,
+}
+
+impl CtPolicyReport {
+    pub fn policy(&self) -> CtPolicy {
+        self.policy
+    }
+
+    pub fn is_compliant(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Embedded timestamps, each with the reason it was not counted, if any.
+    pub fn timestamps(&self) -> &[(SignedCertificateTimestamp, Option<CtTimestampExclusion>)] {
+        self.timestamps.as_slice()
+    }
+
+    pub fn failures(&self) -> &[CtPolicyFailure] {
+        self.failures.as_slice()
+    }
+}
+
+impl FmtDisplay for CtPolicyReport {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        if self.is_compliant() {
+            return write!(formatter, "complies with the {} policy", self.policy)
+        }
+
+        write!(formatter, "does not comply with the {} policy: ", self.policy)?;
+        for (index, failure) in self.failures.iter().enumerate() {
+            if index > 0 {
+                formatter.write_str(", ")?;
+            }
+
+            write!(formatter, "{}", failure)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn evaluate(certificate: &Certificate, issuer: &Certificate, log_list: &CtLogList, policy: CtPolicy, time: DateTime<Utc>) -> CtPolicyReport {
+    let validity = certificate.validity();
+    let verifications = certificate.verify_embedded_timestamps(issuer, log_list.keys().as_slice());
+
+    let mut timestamps = Vec::new();
+    let mut operators = HashSet::new();
+    let (mut counted, mut current) = (0, 0);
+
+    for verification in verifications {
+        let timestamp = verification.timestamp().clone();
+        let log = log_list.find(timestamp.log_id());
+
+        let exclusion = match (log, verification.result()) {
+            (_, Err(TimestampError::UnsupportedVersion)) => Some(CtTimestampExclusion::Invalid(TimestampError::UnsupportedVersion)),
+            (None, _) | (_, Err(TimestampError::UnknownLog)) => Some(CtTimestampExclusion::UnknownLog),
+            (_, Err(error)) => Some(CtTimestampExclusion::Invalid(error)),
+            (Some(log), Ok(..)) => {
+                if timestamp.time() > time {
+                    Some(CtTimestampExclusion::FutureTimestamp)
+                }
+
+                else if log.interval().map(|interval| !interval.contains(validity.time_end())).unwrap_or(false) {
+                    Some(CtTimestampExclusion::OutsideInterval)
+                }
+
+                else {
+
+                    // Each state is taken at the time the timestamp was issued. A read-only log
+                    // was usable before, while a log that became qualified or usable later was not.
+                    let since = log.state_time();
+                    let before = since.map(|since| timestamp.time() < since).unwrap_or(false);
+
+                    match log.state() {
+                        Some(CtLogState::Qualified) |
+                        Some(CtLogState::Usable) if before => Some(CtTimestampExclusion::NotQualified),
+                        Some(CtLogState::Qualified) |
+                        Some(CtLogState::Usable) |
+                        Some(CtLogState::ReadOnly) => {
+                            current += 1;
+                            None
+                        },
+                        Some(CtLogState::Retired) if before => None,
+                        Some(CtLogState::Retired) => Some(CtTimestampExclusion::Retired),
+                        _ => Some(CtTimestampExclusion::NotQualified),
+                    }
+                }
+            },
+        };
+
+        if let (Some(log), None) = (log, exclusion) {
+            operators.insert(log.operator().to_owned());
+            counted += 1;
+        }
+
+        timestamps.push((timestamp, exclusion));
+    }
+
+    let required = if validity.time_end() - validity.time_begin() <= Duration::days(180) { 2 } else { 3 };
+
+    let mut failures = Vec::new();
+    if counted < required {
+        failures.push(CtPolicyFailure::TooFewTimestamps { required, found: counted });
+    }
+
+    if current < policy.required_current() {
+        failures.push(CtPolicyFailure::TooFewCurrentTimestamps { required: policy.required_current(), found: current });
+    }
+
+    if operators.len() < 2 {
+        failures.push(CtPolicyFailure::TooFewOperators { required: 2, found: operators.len() });
+    }
+
+    CtPolicyReport { policy, timestamps, failures }
+}
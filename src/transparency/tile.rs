@@ -0,0 +1,264 @@
+use base64::{
+
+    engine::general_purpose::{STANDARD as BASE64},
+    Engine,
+};
+
+use chrono::{
+
+    DateTime,
+    TimeZone,
+    Utc,
+};
+
+use ring::digest::{
+
+    SHA256,
+    digest,
+};
+
+use crate::{
+
+    transparency::{
+
+        TimestampError,
+        LogEntryType,
+        LogKey,
+        Reader,
+
+        entry::{self},
+        merkle::{self},
+    },
+
+    certificate::{Certificate},
+};
+
+/// Entry of a static CT API data tile.
+#[derive(Clone, Debug)]
+pub struct TileLeaf {
+    timestamp: u64,
+    entry_type: LogEntryType,
+    certificate: Certificate,
+    issuer_key_hash: Option<[u8; 32]>,
+    extensions: Vec<u8>,
+    precertificate: Option<Certificate>,
+    chain: Vec<[u8; 32]>,
+    leaf_input: Vec<u8>,
+}
+
+impl TileLeaf {
+    /// Decode the entries of a data tile, full or partial.
+    pub fn parse_tile(data: &[u8]) -> Option<Vec<TileLeaf>> {
+        let mut reader = Reader::new(data);
+        let mut leaves = Vec::new();
+
+        while !reader.is_empty() {
+            let before = reader.remaining();
+            let (timestamp, entry_type, certificate, issuer_key_hash, extensions) = entry::timestamped_entry(&mut reader)?;
+
+            // The Merkle tree leaf is a version 1 timestamped entry leaf.
+            let mut leaf_input = vec![0, 0];
+            leaf_input.extend_from_slice(&(before[..before.len() - reader.remaining().len()]));
+
+            let precertificate = match entry_type {
+                LogEntryType::Precert => Some(Certificate::parse(reader.vector(3)?)?),
+                LogEntryType::X509 => None,
+            };
+
+            let chain = reader.vector(2)?;
+            if chain.len() % 32 != 0 {
+                return None
+            }
+
+            let chain = chain.chunks(32)
+                .map(|fingerprint| fingerprint.try_into().ok())
+                .collect::<Option<Vec<[u8; 32]>>>()?;
+
+            leaves.push(TileLeaf {
+                timestamp,
+                entry_type,
+                certificate,
+                issuer_key_hash,
+                extensions,
+                precertificate,
+                chain,
+                leaf_input,
+            });
+        }
+
+        Some(leaves)
+    }
+
+    /// Milliseconds since the Unix epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(self.timestamp as i64).single().unwrap_or_default()
+    }
+
+    pub fn entry_type(&self) -> LogEntryType {
+        self.entry_type
+    }
+
+    /// Logged certificate, pending with the final TBS for precertificate entries.
+    pub fn certificate(&self) -> &Certificate {
+        &(self.certificate)
+    }
+
+    /// SHA-256 hash of the issuer public key, for precertificate entries.
+    pub fn issuer_key_hash(&self) -> Option<&[u8]> {
+        self.issuer_key_hash.as_ref()
+            .map(|hash| hash.as_slice())
+    }
+
+    pub fn extensions(&self) -> &[u8] {
+        self.extensions.as_slice()
+    }
+
+    /// Index of the entry in the log, from the `leaf_index` extension.
+    pub fn leaf_index(&self) -> Option<u64> {
+        let mut reader = Reader::new(self.extensions.as_slice());
+
+        while !reader.is_empty() {
+            let extension_type = reader.number(1)?;
+            let data = reader.vector(2)?;
+
+            if extension_type == 0 && data.len() == 5 {
+                return Reader::new(data).number(5)
+            }
+        }
+
+        None
+    }
+
+    /// Signed precertificate submitted to the log, for precertificate entries.
+    pub fn precertificate(&self) -> Option<&Certificate> {
+        self.precertificate.as_ref()
+    }
+
+    /// SHA-256 fingerprints of the chain submitted with the certificate, starting with its issuer.
+    pub fn chain(&self) -> &[[u8; 32]] {
+        self.chain.as_slice()
+    }
+
+    /// Encoded `MerkleTreeLeaf`.
+    pub fn leaf_input(&self) -> &[u8] {
+        self.leaf_input.as_slice()
+    }
+
+    /// Merkle tree hash of the leaf.
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        merkle::leaf_hash(self.leaf_input.as_slice())
+    }
+}
+
+/// Signature line of a signed note.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoteSignature {
+    name: String,
+    key_id: [u8; 4],
+    signature: Vec<u8>,
+}
+
+impl NoteSignature {
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn key_id(&self) -> &[u8] {
+        &(self.key_id)
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        self.signature.as_slice()
+    }
+}
+
+/// Signed note checkpoint of a tiled log.
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    origin: String,
+    size: u64,
+    root_hash: [u8; 32],
+    extensions: Vec<String>,
+    signatures: Vec<NoteSignature>,
+}
+
+impl Checkpoint {
+    /// Parse a checkpoint in the signed note format.
+    pub fn parse(data: &[u8]) -> Option<Checkpoint> {
+        let text = std::str::from_utf8(data).ok()?;
+        let (body, signatures) = text.split_once("\n\n")?;
+
+        let mut lines = body.split('\n');
+        let origin = lines.next()?.to_owned();
+        let size = lines.next()?.parse().ok()?;
+        let root_hash = BASE64.decode(lines.next()?).ok()?
+            .try_into().ok()?;
+
+        let extensions = lines.map(|line| line.to_owned()).collect();
+
+        let signatures = signatures.strip_suffix('\n')?.split('\n').map(|line| {
+            let (name, signature) = line.strip_prefix("\u{2014} ")?.split_once(' ')?;
+            let signature = BASE64.decode(signature).ok()?;
+
+            if signature.len() < 4 {
+                return None
+            }
+
+            Some(NoteSignature {
+                name: name.to_owned(),
+                key_id: signature[..4].try_into().ok()?,
+                signature: signature[4..].to_vec(),
+            })
+        }).collect::<Option<Vec<NoteSignature>>>()?;
+
+        Some(Checkpoint { origin, size, root_hash, extensions, signatures })
+    }
+
+    pub fn origin(&self) -> &str {
+        self.origin.as_str()
+    }
+
+    /// Number of entries in the tree.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn root_hash(&self) -> &[u8] {
+        &(self.root_hash)
+    }
+
+    pub fn extensions(&self) -> &[String] {
+        self.extensions.as_slice()
+    }
+
+    pub fn signatures(&self) -> &[NoteSignature] {
+        self.signatures.as_slice()
+    }
+
+    /// Verify the `RFC6962NoteSignature` of the log with the given key name and key, giving
+    /// the timestamp of the signed tree head.
+    pub fn verify(&self, name: &str, key: &LogKey) -> Result<DateTime<Utc>, TimestampError> {
+        let mut identifier = name.as_bytes().to_vec();
+        identifier.extend_from_slice(&[0x0A, 0x05]);
+        identifier.extend_from_slice(key.public_key());
+
+        let hash = digest(&SHA256, identifier.as_slice());
+        let key_id = &(hash.as_ref()[..4]);
+
+        let signature = self.signatures.iter()
+            .find(|signature| signature.name == name && signature.key_id == key_id)
+            .ok_or(TimestampError::UnknownLog)?;
+
+        let mut reader = Reader::new(signature.signature.as_slice());
+        let timestamp = reader.number(8).ok_or(TimestampError::BadEncoding)?;
+
+        merkle::verify_tree_head(key, timestamp, self.size, &(self.root_hash), reader.remaining())?;
+
+        Utc.timestamp_millis_opt(timestamp as i64).single()
+            .ok_or(TimestampError::BadEncoding)
+    }
+}
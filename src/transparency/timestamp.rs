@@ -0,0 +1,448 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+    },
+
+    error::{Error as StdError},
+};
+
+use ring::digest::{
+
+    SHA256,
+    digest,
+};
+
+use chrono::{
+
+    DateTime,
+    TimeZone,
+    Utc,
+};
+
+use crate::{
+
+    transparency::{
+
+        LogKey,
+        Reader,
+    },
+
+    certificate::{Certificate},
+    signature::{
+
+        SignatureError,
+        KeyAlgorithm,
+        SigningKey,
+        self,
+    },
+    encoding::{self},
+};
+
+/// Version of a signed certificate timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampVersion {
+    V1,
+    Other(u8),
+}
+
+impl TimestampVersion {
+    pub(crate) fn from_code(code: u8) -> TimestampVersion {
+        match code {
+            0 => TimestampVersion::V1,
+            code => TimestampVersion::Other(code),
+        }
+    }
+}
+
+/// Hash algorithm of a signed certificate timestamp signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampHashAlgorithm {
+    None,
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    /// The hash is part of the signature algorithm, as for `Ed25519`.
+    Intrinsic,
+    Other(u8),
+}
+
+impl TimestampHashAlgorithm {
+    pub(crate) fn from_code(code: u8) -> TimestampHashAlgorithm {
+        match code {
+            0 => TimestampHashAlgorithm::None,
+            1 => TimestampHashAlgorithm::Md5,
+            2 => TimestampHashAlgorithm::Sha1,
+            3 => TimestampHashAlgorithm::Sha224,
+            4 => TimestampHashAlgorithm::Sha256,
+            5 => TimestampHashAlgorithm::Sha384,
+            6 => TimestampHashAlgorithm::Sha512,
+            8 => TimestampHashAlgorithm::Intrinsic,
+            code => TimestampHashAlgorithm::Other(code),
+        }
+    }
+}
+
+/// Signature algorithm of a signed certificate timestamp signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampSignatureAlgorithm {
+    Anonymous,
+    Rsa,
+    Dsa,
+    Ecdsa,
+    Ed25519,
+    Other(u8),
+}
+
+impl TimestampSignatureAlgorithm {
+    pub(crate) fn from_code(code: u8) -> TimestampSignatureAlgorithm {
+        match code {
+            0 => TimestampSignatureAlgorithm::Anonymous,
+            1 => TimestampSignatureAlgorithm::Rsa,
+            2 => TimestampSignatureAlgorithm::Dsa,
+            3 => TimestampSignatureAlgorithm::Ecdsa,
+            7 => TimestampSignatureAlgorithm::Ed25519,
+            code => TimestampSignatureAlgorithm::Other(code),
+        }
+    }
+}
+
+/// Reason a signed certificate timestamp could not be verified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampError {
+    /// No key is known for the log that issued the timestamp.
+    UnknownLog,
+    /// The certificate, its issuer or the timestamp is not correctly encoded.
+    BadEncoding,
+    /// The hash and signature algorithm, or its combination with the log key, is not supported.
+    UnsupportedAlgorithm,
+    /// The signature does not match the log key.
+    BadSignature,
+    /// The timestamp is of a version other than 1, whose contents are not known.
+    UnsupportedVersion,
+}
+
+impl FmtDisplay for TimestampError {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.write_str(match self {
+            TimestampError::UnknownLog => "timestamp is issued by an unknown log",
+            TimestampError::BadEncoding => "timestamp or certificate is not correctly encoded",
+            TimestampError::UnsupportedAlgorithm => "timestamp signature algorithm is not supported",
+            TimestampError::BadSignature => "timestamp signature does not match the log key",
+            TimestampError::UnsupportedVersion => "timestamp version is not supported",
+        })
+    }
+}
+
+impl StdError for TimestampError {}
+
+impl From<SignatureError> for TimestampError {
+    fn from(error: SignatureError) -> TimestampError {
+        match error {
+            SignatureError::UnsupportedAlgorithm |
+            SignatureError::AlgorithmMismatch => TimestampError::UnsupportedAlgorithm,
+            SignatureError::BadSignature => TimestampError::BadSignature,
+            SignatureError::BadEncoding |
+            SignatureError::Unsigned => TimestampError::BadEncoding,
+        }
+    }
+}
+
+/// Verdict on a single signed certificate timestamp.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimestampVerification {
+    timestamp: SignedCertificateTimestamp,
+    result: Result<(), TimestampError>,
+}
+
+impl TimestampVerification {
+    pub fn timestamp(&self) -> &SignedCertificateTimestamp {
+        &(self.timestamp)
+    }
+
+    pub fn result(&self) -> Result<(), TimestampError> {
+        self.result
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Signed certificate timestamp, as defined by RFC 6962. Timestamps of versions other than 1
+/// only carry their version and encoding, and cannot be verified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedCertificateTimestamp {
+    version: TimestampVersion,
+    log_id: [u8; 32],
+    timestamp: u64,
+    extensions: Vec<u8>,
+    hash_algorithm: TimestampHashAlgorithm,
+    signature_algorithm: TimestampSignatureAlgorithm,
+    signature: Vec<u8>,
+    digitally_signed: Vec<u8>,
+    raw: Vec<u8>,
+}
+
+impl SignedCertificateTimestamp {
+    /// Parse a single TLS encoded signed certificate timestamp.
+    pub fn parse(data: &[u8]) -> Option<SignedCertificateTimestamp> {
+        let mut reader = Reader::new(data);
+
+        let version = TimestampVersion::from_code(reader.number(1)? as u8);
+        if version != TimestampVersion::V1 {
+            return Some(SignedCertificateTimestamp {
+                version,
+                log_id: [0; 32],
+                timestamp: 0,
+                extensions: Default::default(),
+                hash_algorithm: TimestampHashAlgorithm::None,
+                signature_algorithm: TimestampSignatureAlgorithm::Anonymous,
+                signature: Default::default(),
+                digitally_signed: Default::default(),
+                raw: data.to_vec(),
+            })
+        }
+
+        let log_id = reader.bytes(32)?.try_into().ok()?;
+        let timestamp = reader.number(8)?;
+        let extensions = reader.vector(2)?.to_vec();
+        let digitally_signed = reader.remaining().to_vec();
+        let hash_algorithm = TimestampHashAlgorithm::from_code(reader.number(1)? as u8);
+        let signature_algorithm = TimestampSignatureAlgorithm::from_code(reader.number(1)? as u8);
+        let signature = reader.vector(2)?.to_vec();
+
+        if !reader.is_empty() {
+            return None
+        }
+
+        Some(SignedCertificateTimestamp {
+            version,
+            log_id,
+            timestamp,
+            extensions,
+            hash_algorithm,
+            signature_algorithm,
+            signature,
+            digitally_signed,
+            raw: data.to_vec(),
+        })
+    }
+
+    /// Parse a TLS encoded `SignedCertificateTimestampList`, as carried by the TLS extension.
+    /// Timestamps of versions other than 1 are kept, but cannot be verified.
+    pub fn parse_list(data: &[u8]) -> Option<Vec<SignedCertificateTimestamp>> {
+        let mut reader = Reader::new(data);
+        let mut entries = Reader::new(reader.vector(2)?);
+
+        if !reader.is_empty() {
+            return None
+        }
+
+        let mut timestamps = Vec::new();
+        while !entries.is_empty() {
+            let entry = entries.vector(2)?;
+            timestamps.push(SignedCertificateTimestamp::parse(entry)?);
+        }
+
+        Some(timestamps)
+    }
+
+    /// Parse a `SignedCertificateTimestampList` wrapped in a `DER` encoded `OCTET STRING`,
+    /// as carried by the certificate and `OCSP` response extensions.
+    pub fn parse_der_list(data: &[u8]) -> Option<Vec<SignedCertificateTimestamp>> {
+        let (tag, contents, rest) = encoding::read_element(data)?;
+        if tag != 0x04 || !rest.is_empty() {
+            return None
+        }
+
+        SignedCertificateTimestamp::parse_list(contents)
+    }
+
+    pub fn version(&self) -> TimestampVersion {
+        self.version
+    }
+
+    /// SHA-256 hash of the public key of the log that issued the timestamp.
+    pub fn log_id(&self) -> &[u8] {
+        &(self.log_id)
+    }
+
+    /// Milliseconds since the Unix epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(self.timestamp as i64).single().unwrap_or_default()
+    }
+
+    pub fn extensions(&self) -> &[u8] {
+        self.extensions.as_slice()
+    }
+
+    pub fn hash_algorithm(&self) -> TimestampHashAlgorithm {
+        self.hash_algorithm
+    }
+
+    pub fn signature_algorithm(&self) -> TimestampSignatureAlgorithm {
+        self.signature_algorithm
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        self.signature.as_slice()
+    }
+
+    /// Encoded `digitally-signed` signature, the algorithms followed by the signature.
+    pub fn digitally_signed_encoded(&self) -> &[u8] {
+        self.digitally_signed.as_slice()
+    }
+
+    pub fn raw_encoded(&self) -> &[u8] {
+        self.raw.as_slice()
+    }
+
+    /// Verify the timestamp as issued for the complete certificate, as delivered in the
+    /// TLS extension or an `OCSP` response.
+    pub fn verify_x509_entry(&self, certificate: &Certificate, key: &LogKey) -> Result<(), TimestampError> {
+        self.verify_entry(0, x509_entry(certificate)?.as_slice(), key)
+    }
+
+    /// Verify the timestamp as issued for the precertificate of the certificate, as embedded
+    /// in the certificate. The precertificate is identified by the final TBS and the key hash
+    /// of the issuer.
+    pub fn verify_precert_entry(&self, certificate: &Certificate, issuer: &Certificate, key: &LogKey) -> Result<(), TimestampError> {
+        self.verify_entry(1, precert_entry(certificate, issuer)?.as_slice(), key)
+    }
+
+    pub(crate) fn verify_entry(&self, entry_type: u16, entry: &[u8], key: &LogKey) -> Result<(), TimestampError> {
+        if self.version != TimestampVersion::V1 {
+            return Err(TimestampError::UnsupportedVersion)
+        }
+
+        if key.id() != self.log_id() {
+            return Err(TimestampError::UnknownLog)
+        }
+
+        let algorithm = algorithm_identifier(self.hash_algorithm, self.signature_algorithm)
+            .ok_or(TimestampError::UnsupportedAlgorithm)?;
+
+        let message = signed_data(self.timestamp, entry_type, entry, self.extensions.as_slice())?;
+
+        signature::verify(key.public_key(), algorithm.as_slice(), message.as_slice(), self.signature.as_slice())
+            .map_err(TimestampError::from)
+    }
+
+    /// Issue a timestamp without extensions for an entry, signed with the key of a log.
+    pub(crate) fn sign(key: &SigningKey, timestamp: u64, entry_type: u16, entry: &[u8]) -> Result<SignedCertificateTimestamp, TimestampError> {
+        let log_key = LogKey::new(key.public_key_der_encoded().as_slice())
+            .ok_or(TimestampError::BadEncoding)?;
+
+        let message = signed_data(timestamp, entry_type, entry, &[])?;
+        let signature = key.sign(message.as_slice())?;
+
+        let mut encoded = vec![0];
+        encoded.extend_from_slice(log_key.id());
+        encoded.extend_from_slice(&(timestamp.to_be_bytes()));
+        push_vector(&mut encoded, 2, &[])?;
+        encoded.extend_from_slice(&(digitally_signed(key, signature.as_slice())?));
+
+        SignedCertificateTimestamp::parse(encoded.as_slice())
+            .ok_or(TimestampError::BadEncoding)
+    }
+
+    pub(crate) fn verification(self, keys: &[LogKey], verify: impl Fn(&SignedCertificateTimestamp, &LogKey) -> Result<(), TimestampError>) -> TimestampVerification {
+        let result = match keys.iter().find(|key| key.id() == self.log_id()) {
+            Some(key) => verify(&self, key),
+            None if self.version != TimestampVersion::V1 => Err(TimestampError::UnsupportedVersion),
+            None => Err(TimestampError::UnknownLog),
+        };
+
+        TimestampVerification { timestamp: self, result }
+    }
+}
+
+/// Encoded `x509_entry` of a certificate.
+pub(crate) fn x509_entry(certificate: &Certificate) -> Result<Vec<u8>, TimestampError> {
+    let mut entry = Vec::new();
+    push_vector(&mut entry, 3, certificate.raw_der_encoded())?;
+
+    Ok(entry)
+}
+
+/// Encoded `precert_entry` of a certificate, from its final TBS and the key hash of the issuer.
+pub(crate) fn precert_entry(certificate: &Certificate, issuer: &Certificate) -> Result<Vec<u8>, TimestampError> {
+    let tbs = certificate.final_tbs_der_encoded()
+        .ok_or(TimestampError::BadEncoding)?;
+
+    let issuer_key = issuer.public_key_der_encoded()
+        .ok_or(TimestampError::BadEncoding)?;
+
+    let mut entry = digest(&SHA256, issuer_key.as_slice()).as_ref().to_vec();
+    push_vector(&mut entry, 3, tbs.as_slice())?;
+
+    Ok(entry)
+}
+
+/// Data covered by the signature of a version 1 timestamp.
+fn signed_data(timestamp: u64, entry_type: u16, entry: &[u8], extensions: &[u8]) -> Result<Vec<u8>, TimestampError> {
+    let mut message = vec![0, 0];
+    message.extend_from_slice(&(timestamp.to_be_bytes()));
+    message.extend_from_slice(&(entry_type.to_be_bytes()));
+    message.extend_from_slice(entry);
+    push_vector(&mut message, 2, extensions)?;
+
+    Ok(message)
+}
+
+/// Encoded `digitally-signed` structure of a signature made by the key.
+pub(crate) fn digitally_signed(key: &SigningKey, signature: &[u8]) -> Result<Vec<u8>, TimestampError> {
+    let (hash, algorithm) = match key.algorithm() {
+        KeyAlgorithm::Rsa => (4, 1),
+        KeyAlgorithm::EcdsaP256 => (4, 3),
+        KeyAlgorithm::EcdsaP384 => (5, 3),
+        KeyAlgorithm::Ed25519 => (8, 7),
+    };
+
+    let mut encoded = vec![hash, algorithm];
+    push_vector(&mut encoded, 2, signature)?;
+
+    Ok(encoded)
+}
+
+/// Append a TLS variable length vector with a length prefix of the given width in bytes.
+pub(crate) fn push_vector(data: &mut Vec<u8>, width: usize, contents: &[u8]) -> Result<(), TimestampError> {
+    if width < 8 && contents.len() >> (width * 8) != 0 {
+        return Err(TimestampError::BadEncoding)
+    }
+
+    data.extend_from_slice(&((contents.len() as u64).to_be_bytes()[8 - width..]));
+    data.extend_from_slice(contents);
+
+    Ok(())
+}
+
+/// `DER` encoded algorithm identifier equivalent to a TLS hash and signature algorithm pair.
+pub(crate) fn algorithm_identifier(hash: TimestampHashAlgorithm, signature: TimestampSignatureAlgorithm) -> Option<Vec<u8>> {
+    let (oid, parameters): (&[u8], &[u8]) = match (signature, hash) {
+        (TimestampSignatureAlgorithm::Ecdsa, TimestampHashAlgorithm::Sha256) => (&[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02], &[]),
+        (TimestampSignatureAlgorithm::Ecdsa, TimestampHashAlgorithm::Sha384) => (&[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x03], &[]),
+        (TimestampSignatureAlgorithm::Ecdsa, TimestampHashAlgorithm::Sha512) => (&[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x04], &[]),
+        (TimestampSignatureAlgorithm::Rsa, TimestampHashAlgorithm::Sha256) => (&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B], &[0x05, 0x00]),
+        (TimestampSignatureAlgorithm::Rsa, TimestampHashAlgorithm::Sha384) => (&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0C], &[0x05, 0x00]),
+        (TimestampSignatureAlgorithm::Rsa, TimestampHashAlgorithm::Sha512) => (&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0D], &[0x05, 0x00]),
+        (TimestampSignatureAlgorithm::Ed25519, TimestampHashAlgorithm::Intrinsic) => (&[0x2B, 0x65, 0x70], &[]),
+        _ => return None,
+    };
+
+    let mut contents = encoding::element(0x06, oid);
+    contents.extend_from_slice(parameters);
+
+    Some(encoding::element(0x30, contents.as_slice()))
+}
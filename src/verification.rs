@@ -0,0 +1,490 @@
+use std::{
+
+    fmt::{
+
+        Formatter as FmtFormatter,
+        Display as FmtDisplay,
+        Result as FmtResult,
+    },
+
+    error::{Error as StdError},
+    time::{SystemTime},
+};
+
+use chrono::{
+
+    DateTime,
+    Utc,
+};
+
+use rustls::{
+
+    client::{ServerCertVerifier as TlsServerCertVerifier},
+    server::{ClientCertVerifier as TlsClientCertVerifier},
+
+    CertificateError as TlsCertificateError,
+    Certificate as TlsCertificate,
+    ServerName as TlsServerName,
+    Error as TlsError,
+};
+
+use tls_webpki::{Error as TlsWebPkiError};
+
+use x509_parser::prelude::{
+
+    ExtendedKeyUsage,
+};
+
+use crate::{
+
+    revocation::{
+
+        RevocationOptions,
+        RevocationStatus,
+        OcspResponse,
+        Issuer,
+        policy::{self},
+    },
+
+    store::{
+
+        TrustStoreError,
+        TrustAnchor,
+        TrustStore,
+    },
+
+    transparency::{
+
+        SignedCertificateTimestamp,
+        LogKey,
+    },
+
+    certificate::{Certificate},
+    path::{self},
+};
+
+/// Identity and stapled data of a server whose certificate chain is verified.
+#[derive(Clone, Debug)]
+pub struct ServerVerification {
+    name: TlsServerName,
+    ocsp_response: Option<Vec<u8>>,
+    signed_timestamps: Vec<Vec<u8>>,
+    log_keys: Vec<LogKey>,
+}
+
+impl ServerVerification {
+    /// Create server verification for a `DNS` name or `IP` address.
+    pub fn new(name: &str) -> Option<ServerVerification> {
+        if let Ok(name) = TlsServerName::try_from(name) {
+            return Some(ServerVerification {
+                name,
+                ocsp_response: None,
+                signed_timestamps: Default::default(),
+                log_keys: Default::default(),
+            })
+        }
+
+        None
+    }
+
+    /// Attach a stapled `OCSP` response. It must be signed for the issuer of the server
+    /// certificate and current, and verification fails when it reports the certificate revoked.
+    pub fn with_ocsp_response(mut self, response: &[u8]) -> ServerVerification {
+        self.ocsp_response = Some(response.to_vec());
+        self
+    }
+
+    /// Attach a signed certificate timestamp delivered alongside the chain. It must be
+    /// issued for the server certificate by one of the attached log keys.
+    pub fn with_signed_timestamp(mut self, timestamp: &[u8]) -> ServerVerification {
+        self.signed_timestamps.push(timestamp.to_vec());
+        self
+    }
+
+    /// Attach the key of a log trusted to issue delivered signed certificate timestamps.
+    pub fn with_log_key(mut self, key: LogKey) -> ServerVerification {
+        self.log_keys.push(key);
+        self
+    }
+
+    pub fn name(&self) -> String {
+        match self.name {
+            TlsServerName::DnsName(ref name) => name.as_ref().to_owned(),
+            TlsServerName::IpAddress(ref address) => address.to_string(),
+            _ => Default::default(),
+        }
+    }
+
+    pub fn ocsp_response(&self) -> Option<&[u8]> {
+        if let Some(ref ocsp_response) = self.ocsp_response {
+            return Some(ocsp_response.as_slice())
+        }
+
+        None
+    }
+
+    pub fn signed_timestamps(&self) -> &[Vec<u8>] {
+        self.signed_timestamps.as_slice()
+    }
+
+    pub fn log_keys(&self) -> &[LogKey] {
+        self.log_keys.as_slice()
+    }
+}
+
+/// Reason a certificate chain failed to verify.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationReason {
+    /// The trust anchors could not be loaded.
+    TrustStore,
+    BadEncoding,
+    Expired,
+    NotValidYet,
+    UnknownIssuer,
+    BadSignature,
+    UnsupportedSignature,
+    /// A certificate in the chain does not allow the required extended key usage.
+    InvalidPurpose,
+    NotValidForName,
+    /// The stapled `OCSP` response is malformed, not signed for the issuer, not current or
+    /// not about the certificate.
+    InvalidOcspResponse,
+    /// A delivered signed certificate timestamp is malformed, from an unknown log or not
+    /// signed for the certificate.
+    InvalidSignedTimestamp,
+    /// A basic, path length or name constraint was violated.
+    InvalidConstraints,
+    Revoked,
+    /// No usable revocation data is available for a certificate the revocation policy covers.
+    RevocationUnknown,
+    Other,
+}
+
+impl FmtDisplay for VerificationReason {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        formatter.write_str(match self {
+            VerificationReason::TrustStore => "trust store could not be loaded",
+            VerificationReason::BadEncoding => "certificate is not correctly encoded",
+            VerificationReason::Expired => "certificate has expired",
+            VerificationReason::NotValidYet => "certificate is not valid yet",
+            VerificationReason::UnknownIssuer => "certificate is issued by an unknown issuer",
+            VerificationReason::BadSignature => "certificate has an invalid signature",
+            VerificationReason::UnsupportedSignature => "certificate has an unsupported signature algorithm",
+            VerificationReason::InvalidPurpose => "certificate is not valid for the required purpose",
+            VerificationReason::NotValidForName => "certificate is not valid for the server name",
+            VerificationReason::InvalidOcspResponse => "stapled OCSP response is not valid for the certificate",
+            VerificationReason::InvalidSignedTimestamp => "delivered signed certificate timestamp is not valid for the certificate",
+            VerificationReason::InvalidConstraints => "certificate chain violates its constraints",
+            VerificationReason::Revoked => "certificate has been revoked",
+            VerificationReason::RevocationUnknown => "revocation status of certificate is unknown",
+            VerificationReason::Other => "certificate chain could not be verified",
+        })
+    }
+}
+
+/// Failed chain verification, with the depth of the offending certificate when known.
+///
+/// Depth `0` is the verified certificate itself, followed by its issuers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationError {
+    reason: VerificationReason,
+    depth: Option<usize>,
+}
+
+impl VerificationError {
+    pub(crate) fn new(reason: VerificationReason, depth: Option<usize>) -> VerificationError {
+        VerificationError { reason, depth }
+    }
+
+    pub fn reason(&self) -> VerificationReason {
+        self.reason
+    }
+
+    pub fn depth(&self) -> Option<usize> {
+        self.depth
+    }
+}
+
+impl FmtDisplay for VerificationError {
+    fn fmt(&self, formatter: &mut FmtFormatter<'_>) -> FmtResult {
+        if let Some(depth) = self.depth {
+            return write!(formatter, "{} (depth {})", self.reason, depth)
+        }
+
+        write!(formatter, "{}", self.reason)
+    }
+}
+
+impl StdError for VerificationError {}
+
+impl From<TrustStoreError> for VerificationError {
+    fn from(_: TrustStoreError) -> VerificationError {
+        VerificationError::new(VerificationReason::TrustStore, None)
+    }
+}
+
+/// Successful chain verification.
+#[derive(Clone, Debug)]
+pub struct VerificationReport {
+    path: Vec<Certificate>,
+    anchor: TrustAnchor,
+    revocation: Vec<RevocationStatus>,
+}
+
+impl VerificationReport {
+    pub(crate) fn new(path: Vec<Certificate>, anchor: TrustAnchor) -> VerificationReport {
+        VerificationReport { path, anchor, revocation: Default::default() }
+    }
+
+    /// Validated path, starting with the verified certificate and ending with
+    /// the certificate issued by the trust anchor. Each certificate is checked to be
+    /// signed by the next one.
+    pub fn path(&self) -> &[Certificate] {
+        self.path.as_slice()
+    }
+
+    /// Trust anchor the path terminates in, if it is known as a certificate.
+    pub fn anchor(&self) -> Option<&Certificate> {
+        self.anchor.certificate.as_ref()
+    }
+
+    /// Issuer of the last certificate in the path, the trust anchor, which may be known
+    /// only by its subject and public key.
+    pub(crate) fn anchor_issuer(&self) -> Issuer<'_> {
+        Issuer::from_anchor(&(self.anchor))
+    }
+
+    /// Revocation status of each certificate in the path, in path order. Empty when the
+    /// chain was verified without revocation checking.
+    pub fn revocation(&self) -> &[RevocationStatus] {
+        self.revocation.as_slice()
+    }
+}
+
+fn tls_chain(chain: &[Certificate]) -> Vec<TlsCertificate> {
+    chain.iter().map(|certificate| {
+        TlsCertificate(certificate.raw_der_encoded().to_vec())
+    }).collect()
+}
+
+pub(crate) fn issued_by(certificate: &Certificate, issuer: &Certificate) -> bool {
+    if certificate.issuer_der_encoded() != issuer.subject_der_encoded() {
+        return false
+    }
+
+    if let (Some(authority), Some(subject)) = (certificate.authority_key_identifier(), issuer.subject_key_identifier()) {
+        return authority == subject
+    }
+
+    true
+}
+
+/// Best-effort reconstruction of the path of a chain that failed to verify, matching
+/// issuers by name and key identifier only. It locates the offending certificate and is
+/// never reported as validated.
+fn assemble_path(certificate: &Certificate, chain: &[Certificate], trust_store: &TrustStore) -> (Vec<Certificate>, Option<Certificate>) {
+    let mut path = vec![certificate.clone()];
+    let mut remaining: Vec<&Certificate> = chain.iter().collect();
+
+    while let Some(current) = path.last() {
+        let issuer = current.issuer_der_encoded().unwrap_or_default();
+
+        let mut anchors = trust_store.anchors().iter()
+            .filter(|anchor| anchor.is_named(issuer.as_slice()))
+            .peekable();
+
+        if anchors.peek().is_some() {
+            let anchor = anchors.filter_map(|anchor| anchor.certificate.as_ref())
+                .find(|root| issued_by(current, root))
+                .cloned();
+
+            return (path, anchor)
+        }
+
+        if let Some(position) = remaining.iter().position(|candidate| issued_by(current, candidate)) {
+            path.push(remaining.remove(position).clone());
+            continue
+        }
+
+        break
+    }
+
+    (path, None)
+}
+
+fn disallows_purpose(certificate: &Certificate, purpose: &Purpose<'_>) -> bool {
+    if let Some(certificate) = certificate.tbs_certificate() {
+        if let Ok(Some(extension)) = certificate.extended_key_usage() {
+            return !purpose.allows(extension.value)
+        }
+    }
+
+    false
+}
+
+fn failure(error: TlsError, path: &[Certificate], anchor: Option<&Certificate>, purpose: &Purpose<'_>, time: DateTime<Utc>) -> VerificationError {
+    let expired = || path.iter().position(|certificate| {
+        certificate.validity().time_end() < time
+    });
+
+    let not_valid_yet = || path.iter().position(|certificate| {
+        certificate.validity().time_begin() > time
+    });
+
+    let bad_signature = || path.iter().enumerate().position(|(depth, certificate)| {
+        match path.get(depth + 1).or(anchor) {
+            Some(issuer) => certificate.verify_signed_by(issuer).is_err(),
+            None => false,
+        }
+    });
+
+    let (reason, depth) = match error {
+        TlsError::InvalidCertificate(error) => match error {
+            TlsCertificateError::BadEncoding => (VerificationReason::BadEncoding, None),
+            TlsCertificateError::Expired => (VerificationReason::Expired, expired()),
+            TlsCertificateError::NotValidYet => (VerificationReason::NotValidYet, not_valid_yet()),
+            TlsCertificateError::Revoked => (VerificationReason::Revoked, None),
+            TlsCertificateError::UnknownIssuer => (VerificationReason::UnknownIssuer, Some(path.len() - 1)),
+            TlsCertificateError::BadSignature => (VerificationReason::BadSignature, bad_signature()),
+            TlsCertificateError::NotValidForName => (VerificationReason::NotValidForName, Some(0)),
+            TlsCertificateError::InvalidPurpose => (VerificationReason::InvalidPurpose, None),
+            TlsCertificateError::Other(ref error) => match error.downcast_ref::<TlsWebPkiError>() {
+                Some(TlsWebPkiError::RequiredEkuNotFound) => (VerificationReason::InvalidPurpose, {
+                    path.iter().position(|certificate| disallows_purpose(certificate, purpose))
+                }),
+                Some(TlsWebPkiError::CaUsedAsEndEntity) => (VerificationReason::InvalidConstraints, Some(0)),
+                Some(TlsWebPkiError::EndEntityUsedAsCa) => (VerificationReason::InvalidConstraints, {
+                    path.iter().skip(1).position(|certificate| !certificate.authority())
+                        .map(|position| position + 1)
+                }),
+                Some(TlsWebPkiError::PathLenConstraintViolated) |
+                Some(TlsWebPkiError::NameConstraintViolation) => (VerificationReason::InvalidConstraints, None),
+                Some(TlsWebPkiError::UnsupportedSignatureAlgorithm) |
+                Some(TlsWebPkiError::UnsupportedSignatureAlgorithmForPublicKey) => (VerificationReason::UnsupportedSignature, None),
+                Some(TlsWebPkiError::UnsupportedCriticalExtension) |
+                Some(TlsWebPkiError::UnsupportedCertVersion) |
+                Some(TlsWebPkiError::ExtensionValueInvalid) |
+                Some(TlsWebPkiError::MalformedExtensions) => (VerificationReason::BadEncoding, None),
+                _ => (VerificationReason::Other, None),
+            },
+            _ => (VerificationReason::Other, None),
+        },
+        _ => (VerificationReason::Other, None),
+    };
+
+    VerificationError::new(reason, depth)
+}
+
+pub(crate) enum Purpose<'a> {
+    Server(&'a ServerVerification),
+    Client,
+}
+
+impl<'a> Purpose<'a> {
+    fn allows(&self, usage: &ExtendedKeyUsage) -> bool {
+        match self {
+            Purpose::Server(..) => usage.server_auth,
+            Purpose::Client => usage.client_auth,
+        }
+    }
+}
+
+pub(crate) fn check(trust_store: &TrustStore, certificate: &Certificate, chain: &[Certificate], purpose: &Purpose<'_>, time: DateTime<Utc>) -> Result<(), TlsError> {
+    let end_entity = &TlsCertificate(certificate.raw_der_encoded().to_owned());
+    let intermediates = tls_chain(chain);
+
+    match purpose {
+        Purpose::Server(server) => {
+            // The verifier ignores stapled data, which is checked separately.
+            trust_store.server.verify_server_cert(end_entity, intermediates.as_slice(), &(server.name),
+                &mut std::iter::empty(), &[], SystemTime::from(time))
+                .map(|_| ())
+        },
+        Purpose::Client => {
+            trust_store.client.verify_client_cert(end_entity, intermediates.as_slice(), SystemTime::from(time))
+                .map(|_| ())
+        },
+    }
+}
+
+/// Check a stapled `OCSP` response against the issuer of the verified certificate.
+fn check_stapled(report: &VerificationReport, response: &[u8], time: DateTime<Utc>) -> Result<(), VerificationError> {
+    let invalid = || VerificationError::new(VerificationReason::InvalidOcspResponse, Some(0));
+    let path = report.path();
+
+    let issuer = match path.get(1) {
+        Some(issuer) => Issuer::from_certificate(issuer).ok_or_else(invalid)?,
+        None => report.anchor_issuer(),
+    };
+
+    let response = OcspResponse::parse(response)
+        .ok_or_else(invalid)?;
+
+    response.verify_issued_at(&issuer, time)
+        .map_err(|_| invalid())?;
+
+    let single = response.find_issued(&(path[0]), &issuer)
+        .filter(|single| single.is_fresh_at(time))
+        .ok_or_else(invalid)?;
+
+    if let RevocationStatus::Revoked(..) = single.status() {
+        return Err(VerificationError::new(VerificationReason::Revoked, Some(0)))
+    }
+
+    Ok(())
+}
+
+/// Check signed certificate timestamps delivered alongside the chain against the log keys.
+fn check_delivered(certificate: &Certificate, server: &ServerVerification) -> Result<(), VerificationError> {
+    let invalid = || VerificationError::new(VerificationReason::InvalidSignedTimestamp, Some(0));
+
+    let timestamps: Vec<SignedCertificateTimestamp> = server.signed_timestamps().iter()
+        .map(|timestamp| SignedCertificateTimestamp::parse(timestamp.as_slice()))
+        .collect::<Option<_>>()
+        .ok_or_else(invalid)?;
+
+    if !certificate.verify_delivered_timestamps(timestamps.as_slice(), server.log_keys()).iter().all(|verification| verification.is_valid()) {
+        return Err(invalid())
+    }
+
+    Ok(())
+}
+
+pub(crate) fn verify(trust_store: &TrustStore, certificate: &Certificate, chain: &[Certificate], purpose: &Purpose<'_>, time: DateTime<Utc>) -> Result<VerificationReport, VerificationError> {
+    let report = match path::build(certificate, chain, trust_store, purpose, time).into_iter().next() {
+        Some(report) => report,
+        None => {
+            let (path, anchor) = assemble_path(certificate, chain, trust_store);
+
+            if let Err(error) = check(trust_store, certificate, chain, purpose, time) {
+                return Err(failure(error, path.as_slice(), anchor.as_ref(), purpose, time))
+            }
+
+            return Err(VerificationError::new(VerificationReason::Other, None))
+        },
+    };
+
+    if let Purpose::Server(server) = purpose {
+        if let Some(response) = server.ocsp_response() {
+            check_stapled(&report, response, time)?;
+        }
+
+        check_delivered(certificate, server)?;
+    }
+
+    Ok(report)
+}
+
+pub(crate) fn verify_with_revocation(trust_store: &TrustStore, certificate: &Certificate, chain: &[Certificate], purpose: &Purpose<'_>, revocation: &RevocationOptions, time: DateTime<Utc>) -> Result<VerificationReport, VerificationError> {
+    let stapled = match purpose {
+        Purpose::Server(server) => server.ocsp_response().and_then(OcspResponse::parse),
+        Purpose::Client => None,
+    };
+
+    // The path of the report is built with every signature checked, so a decoy with the
+    // name and key identifier of an issuer cannot stand in for it.
+    let mut report = verify(trust_store, certificate, chain, purpose, time)?;
+    report.revocation = policy::evaluate(report.path(), &(report.anchor_issuer()), revocation, stapled.as_ref(), time)?;
+
+    Ok(report)
+}